@@ -0,0 +1,231 @@
+use crate::common::BACK_UP_FOLDER;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "minipp.manifest.json";
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MovedFile {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DeleteManifest {
+    pub files: Vec<MovedFile>,
+}
+
+impl DeleteManifest {
+    pub fn path(project_root: &str) -> PathBuf {
+        PathBuf::from(project_root)
+            .join(BACK_UP_FOLDER)
+            .join(MANIFEST_FILE)
+    }
+
+    pub fn load(project_root: &str) -> io::Result<Self> {
+        match fs::read_to_string(Self::path(project_root)) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, project_root: &str) -> io::Result<()> {
+        let manifest_path = Self::path(project_root);
+        if self.files.is_empty() {
+            // 没有待恢复的文件时不保留清单
+            return match fs::remove_file(manifest_path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = manifest_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(manifest_path, json)
+    }
+}
+
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)
+}
+
+// 将未使用的文件按原有相对路径移动到备份目录，并把移动记录追加到清单中以便恢复
+pub fn move_to_backup(project_root: &str, files: &HashSet<String>) -> io::Result<Vec<MovedFile>> {
+    let root = Path::new(project_root);
+    let mut manifest = DeleteManifest::load(project_root)?;
+    let mut sorted_files: Vec<_> = files.iter().collect();
+    sorted_files.sort();
+
+    let mut moved = Vec::new();
+    for file in sorted_files {
+        let to = Path::new(BACK_UP_FOLDER).join(file);
+        let to_str = to.to_string_lossy().replace('\\', "/");
+        if root.join(&to).exists() {
+            println!("跳过 {}: 备份目录中已存在 {}", file, to_str);
+            continue;
+        }
+        match move_file(&root.join(file), &root.join(&to)) {
+            Ok(()) => moved.push(MovedFile {
+                from: file.to_string(),
+                to: to_str,
+            }),
+            Err(e) => println!("移动 {} 失败: {:?}", file, e),
+        }
+    }
+
+    manifest.files.extend(moved.iter().cloned());
+    manifest.save(project_root)?;
+    Ok(moved)
+}
+
+#[derive(Default, Debug)]
+pub struct RestoreResult {
+    pub restored: Vec<MovedFile>,
+    pub conflicts: Vec<MovedFile>,
+    pub missing: Vec<MovedFile>,
+}
+
+// 删除备份目录中因移回文件而留下的空文件夹
+fn remove_empty_dirs(dir: &Path) -> io::Result<bool> {
+    let mut is_empty = true;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() || !remove_empty_dirs(&path)? {
+            is_empty = false;
+        }
+    }
+    if is_empty {
+        fs::remove_dir(dir)?;
+    }
+    Ok(is_empty)
+}
+
+// 根据清单把备份目录中的文件移回原位置，原位置已有新文件时视为冲突，不覆盖
+pub fn restore_from_backup(project_root: &str) -> io::Result<RestoreResult> {
+    let root = Path::new(project_root);
+    let manifest = DeleteManifest::load(project_root)?;
+    let mut result = RestoreResult::default();
+    let mut remaining = DeleteManifest::default();
+
+    for moved_file in manifest.files {
+        let from = root.join(&moved_file.from);
+        let to = root.join(&moved_file.to);
+        if !to.exists() {
+            result.missing.push(moved_file);
+        } else if from.exists() {
+            result.conflicts.push(moved_file.clone());
+            remaining.files.push(moved_file);
+        } else {
+            match move_file(&to, &from) {
+                Ok(()) => result.restored.push(moved_file),
+                Err(e) => {
+                    println!("恢复 {} 失败: {:?}", moved_file.from, e);
+                    remaining.files.push(moved_file);
+                }
+            }
+        }
+    }
+
+    remaining.save(project_root)?;
+    let backup_folder = root.join(BACK_UP_FOLDER);
+    if backup_folder.is_dir() {
+        remove_empty_dirs(&backup_folder)?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("minipp-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/components")).unwrap();
+        fs::write(dir.join("src/a.ts"), "export const a = 1;").unwrap();
+        fs::write(dir.join("src/components/b.tsx"), "export const b = 2;").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_move_to_backup() {
+        let dir = temp_project("move");
+        let root = dir.to_str().unwrap();
+        let files = HashSet::from(["src/a.ts", "src/components/b.tsx"].map(String::from));
+
+        let moved = move_to_backup(root, &files).unwrap();
+        assert_eq!(moved.len(), 2);
+        assert!(!dir.join("src/a.ts").exists());
+        assert!(
+            dir.join(BACK_UP_FOLDER)
+                .join("src/components/b.tsx")
+                .exists()
+        );
+
+        let manifest = DeleteManifest::load(root).unwrap();
+        assert_eq!(
+            manifest.files[0],
+            MovedFile {
+                from: "src/a.ts".to_string(),
+                to: format!("{}/src/a.ts", BACK_UP_FOLDER),
+            }
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_move_to_backup_appends_manifest() {
+        let dir = temp_project("append");
+        let root = dir.to_str().unwrap();
+
+        move_to_backup(root, &HashSet::from(["src/a.ts".to_string()])).unwrap();
+        move_to_backup(root, &HashSet::from(["src/components/b.tsx".to_string()])).unwrap();
+
+        let manifest = DeleteManifest::load(root).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_from_backup() {
+        let dir = temp_project("restore");
+        let root = dir.to_str().unwrap();
+        let files = HashSet::from(["src/a.ts", "src/components/b.tsx"].map(String::from));
+        move_to_backup(root, &files).unwrap();
+
+        let result = restore_from_backup(root).unwrap();
+        assert_eq!(result.restored.len(), 2);
+        assert!(result.conflicts.is_empty());
+        assert!(dir.join("src/a.ts").exists());
+        assert!(dir.join("src/components/b.tsx").exists());
+        assert!(!dir.join(BACK_UP_FOLDER).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_from_backup_conflict() {
+        let dir = temp_project("conflict");
+        let root = dir.to_str().unwrap();
+        let files = HashSet::from(["src/a.ts", "src/components/b.tsx"].map(String::from));
+        move_to_backup(root, &files).unwrap();
+        fs::write(dir.join("src/a.ts"), "export const a = 'new';").unwrap();
+
+        let result = restore_from_backup(root).unwrap();
+        assert_eq!(result.restored.len(), 1);
+        assert_eq!(result.conflicts[0].from, "src/a.ts");
+        assert_eq!(
+            fs::read_to_string(dir.join("src/a.ts")).unwrap(),
+            "export const a = 'new';"
+        );
+        assert_eq!(DeleteManifest::load(root).unwrap().files, result.conflicts);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
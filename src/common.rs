@@ -1,8 +1,10 @@
 use crate::with_dot;
-use ignore::gitignore::GitignoreBuilder;
-use serde::Deserialize;
+use glob::{MatchOptions, Pattern, glob};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use path_clean::clean;
+use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::{env, fs, io};
 
@@ -12,20 +14,126 @@ pub struct ProjectDependencies {
     dependencies: Option<HashMap<String, String>>,
     #[serde(rename = "devDependencies")]
     dev_dependencies: Option<HashMap<String, String>>,
+    #[serde(rename = "peerDependencies")]
+    peer_dependencies: Option<HashMap<String, String>>,
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: Option<HashMap<String, String>>,
 }
 
 impl ProjectDependencies {
+    // 按优先级从低到高排列的各个依赖字段
+    fn sections(&self) -> [(DependencyType, &Option<HashMap<String, String>>); 4] {
+        [
+            (DependencyType::DevDependencies, &self.dev_dependencies),
+            (DependencyType::PeerDependencies, &self.peer_dependencies),
+            (
+                DependencyType::OptionalDependencies,
+                &self.optional_dependencies,
+            ),
+            (DependencyType::Dependencies, &self.dependencies),
+        ]
+    }
+
     pub fn all_dependencies(&self) -> HashSet<String> {
         let mut set: HashSet<String> = HashSet::new();
-        for map_option in [&self.dependencies, &self.dev_dependencies] {
-            if let Some(map) = map_option {
-                for (k, _) in map {
-                    set.insert(k.to_string());
-                }
+        for (_, map) in self.sections() {
+            for k in map.iter().flat_map(|map| map.keys()) {
+                set.insert(k.to_string());
             }
         }
         set
     }
+
+    // 同时出现在多处时优先级为 dependencies > optionalDependencies > peerDependencies > devDependencies
+    pub fn dependency_types(&self) -> HashMap<String, DependencyType> {
+        let mut types = HashMap::new();
+        for (dependency_type, map) in self.sections() {
+            for name in map.iter().flat_map(|map| map.keys()) {
+                types.insert(name.to_string(), dependency_type);
+            }
+        }
+        types
+    }
+
+    // 每个依赖出现在哪些字段中
+    pub fn dependency_categories(&self) -> BTreeMap<String, BTreeSet<DependencyType>> {
+        let mut categories: BTreeMap<String, BTreeSet<DependencyType>> = BTreeMap::new();
+        for (dependency_type, map) in self.sections() {
+            for name in map.iter().flat_map(|map| map.keys()) {
+                categories
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(dependency_type);
+            }
+        }
+        categories
+    }
+
+    // 出现在多个字段中且版本范围没有交集的依赖，以及各字段中的版本范围
+    pub fn version_conflicts(&self) -> BTreeMap<String, BTreeMap<DependencyType, String>> {
+        let mut ranges: BTreeMap<String, BTreeMap<DependencyType, String>> = BTreeMap::new();
+        for (dependency_type, map) in self.sections() {
+            for (name, range) in map.iter().flatten() {
+                ranges
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(dependency_type, range.to_string());
+            }
+        }
+        ranges.retain(|_, ranges| {
+            let majors: Vec<_> = ranges
+                .values()
+                .map(|range| get_range_majors(range))
+                .collect();
+            majors.iter().enumerate().any(|(index, a)| {
+                majors[index + 1..].iter().any(|b| match (a, b) {
+                    (Some(a), Some(b)) => a.is_disjoint(b),
+                    _ => false,
+                })
+            })
+        });
+        ranges
+    }
+}
+
+// 版本范围允许的主版本号，如 ^18 || ^19.2.0 -> {18, 19}
+// >=、区间、*、workspace: 与 git 地址等无法只凭主版本号判断，返回 None
+fn get_range_majors(range: &str) -> Option<BTreeSet<u64>> {
+    range
+        .split("||")
+        .map(|alternative| {
+            let version = alternative
+                .trim()
+                .trim_start_matches(['^', '~', '='])
+                .trim_start_matches('v');
+            if version.contains(char::is_whitespace) {
+                return None;
+            }
+            let major = version.split('.').next()?;
+            major.parse().ok()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyType {
+    Dependencies,
+    DevDependencies,
+    PeerDependencies,
+    OptionalDependencies,
+}
+
+impl DependencyType {
+    // package.json 中对应的字段名
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            DependencyType::Dependencies => "dependencies",
+            DependencyType::DevDependencies => "devDependencies",
+            DependencyType::PeerDependencies => "peerDependencies",
+            DependencyType::OptionalDependencies => "optionalDependencies",
+        }
+    }
 }
 
 #[derive(Default, Debug, Deserialize, PartialEq)]
@@ -38,19 +146,125 @@ pub struct MinippConfig {
     pub ignore_files: Option<Vec<String>>,
     #[serde(rename = "ignoreDependencies")]
     pub ignore_dependencies: Option<Vec<String>>,
+    #[serde(rename = "include")]
+    pub include: Option<Vec<String>>,
+    #[serde(rename = "entries")]
+    pub entries: Option<Vec<String>>,
+    #[serde(rename = "devFiles")]
+    pub dev_files: Option<Vec<String>>,
+}
+
+pub const DEFAULT_INCLUDE: &str = "src";
+
+// 测试、stories 与配置文件不属于生产代码
+pub const DEFAULT_DEV_FILES: [&str; 7] = [
+    "*.test.*",
+    "*.spec.*",
+    "*.stories.*",
+    "__tests__/",
+    "__mocks__/",
+    "*.config.*",
+    ".*rc.*",
+];
+
+// 扫描根目录，支持 glob，例如 packages/*/src
+#[derive(Debug, Clone)]
+pub struct IncludeMatcher {
+    dirs: Vec<String>,
+    patterns: Vec<Pattern>,
+}
+
+impl Default for IncludeMatcher {
+    fn default() -> Self {
+        Self::new(&[DEFAULT_INCLUDE.to_string()])
+    }
+}
+
+impl IncludeMatcher {
+    pub fn new(dirs: &[String]) -> Self {
+        let dirs: Vec<String> = dirs
+            .iter()
+            .map(|dir| {
+                dir.trim_start_matches("./")
+                    .trim_end_matches('/')
+                    .to_string()
+            })
+            .filter(|dir| !dir.is_empty())
+            .collect();
+        let patterns = dirs
+            .iter()
+            .filter_map(|dir| Pattern::new(dir).ok())
+            .collect();
+        Self { dirs, patterns }
+    }
+
+    pub fn glob_patterns(&self, ext: &str) -> Vec<String> {
+        self.dirs
+            .iter()
+            .map(|dir| format!("{}/**/*.{}", dir, ext))
+            .collect()
+    }
+
+    pub fn is_included(&self, path: &str) -> bool {
+        Path::new(path)
+            .ancestors()
+            .any(|ancestor| self.is_root(&ancestor.to_string_lossy()))
+    }
+
+    // 判断目录本身是否为某个扫描根目录
+    pub fn is_root(&self, dir: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(dir, options))
+    }
+}
+
+impl MinippConfig {
+    pub fn ignore_files_matcher(&self) -> Gitignore {
+        build_gitignore(self.ignore_files.as_deref().unwrap_or_default())
+    }
+
+    pub fn dev_files_matcher(&self) -> Gitignore {
+        match &self.dev_files {
+            Some(dev_files) => build_gitignore(dev_files),
+            None => build_gitignore(&DEFAULT_DEV_FILES.map(String::from)),
+        }
+    }
+
+    pub fn include_matcher(&self) -> IncludeMatcher {
+        match &self.include {
+            Some(include) if !include.is_empty() => IncludeMatcher::new(include),
+            _ => IncludeMatcher::default(),
+        }
+    }
+
+    // ignoreExt 中的后缀统一去掉开头的点并转为小写，"png" 与 ".png" 等价
+    pub fn ignored_exts(&self) -> HashSet<String> {
+        self.ignore_ext
+            .iter()
+            .flatten()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect()
+    }
 }
 
 pub const BACK_UP_FOLDER: &str = "minipp-delete-files";
 
-pub const SUPPORT_FILE_TYPES: [&str; 18] = [
-    "ts", "tsx", "less", "scss", "css", "png", "jpg", "jpeg", "gif", "svg", "mp3", "mp4", "wav",
-    "woff", "woff2", "ttf", "eot", "json",
+pub const SUPPORT_FILE_TYPES: [&str; 27] = [
+    "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro", "less", "scss",
+    "css", "png", "jpg", "jpeg", "gif", "svg", "mp3", "mp4", "wav", "woff", "woff2", "ttf", "eot",
+    "json",
 ];
 
 // This is just for the convenience of copying, of course, it is completely possible to write them one by one instead of using macro_rules.
-pub const SUPPORT_FILE_TYPES_WITH_DOT: [&str; 18] = with_dot![
-    "ts", "tsx", "less", "scss", "css", "png", "jpg", "jpeg", "gif", "svg", "mp3", "mp4", "wav",
-    "woff", "woff2", "ttf", "eot", "json"
+pub const SUPPORT_FILE_TYPES_WITH_DOT: [&str; 27] = with_dot![
+    "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro", "less", "scss",
+    "css", "png", "jpg", "jpeg", "gif", "svg", "mp3", "mp4", "wav", "woff", "woff2", "ttf", "eot",
+    "json"
 ];
 
 pub fn get_project_dependencies(project_root: &str) -> HashSet<String> {
@@ -58,16 +272,118 @@ pub fn get_project_dependencies(project_root: &str) -> HashSet<String> {
     let package_json_str =
         fs::read_to_string(package_json_path).expect("Unable to read package.json");
     let pkg_json: ProjectDependencies = serde_json::from_str(package_json_str.as_str()).unwrap();
-    let dependencies_info = ProjectDependencies {
-        dependencies: pkg_json.dependencies,
-        dev_dependencies: pkg_json.dev_dependencies,
-    };
-    dependencies_info.all_dependencies()
+    pkg_json.all_dependencies()
+}
+
+// package.json 不存在或无法解析时返回空的依赖信息
+pub fn read_project_dependencies(project_root: &str) -> ProjectDependencies {
+    fs::read_to_string(Path::new(project_root).join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<ProjectDependencies>(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn get_project_dependency_types(project_root: &str) -> HashMap<String, DependencyType> {
+    read_project_dependencies(project_root).dependency_types()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Analyze,
+    Restore,
+}
+
+pub const RESTORE_COMMAND: &str = "restore";
+
+// args 不包括程序名。minipp restore <path> 总是恢复；单独的 restore 在存在同名目录时分析该目录，
+// 此时可以用 minipp restore . 恢复当前目录，或用 minipp ./restore 明确分析该目录
+fn parse_command(args: &[String], is_dir: impl Fn(&str) -> bool) -> Command {
+    match args {
+        [arg1] if arg1 == RESTORE_COMMAND && is_dir(arg1) => Command::Analyze,
+        [arg1, ..] if arg1 == RESTORE_COMMAND => Command::Restore,
+        _ => Command::Analyze,
+    }
+}
+
+pub fn get_command() -> Command {
+    let args: Vec<String> = env::args().skip(1).collect();
+    parse_command(&args, |path| Path::new(path).is_dir())
+}
+
+// 若 i 处是注释，返回注释结束后的位置
+fn skip_comment(chars: &[char], i: usize) -> Option<usize> {
+    match (chars.get(i), chars.get(i + 1)) {
+        (Some('/'), Some('/')) => Some(
+            chars[i..]
+                .iter()
+                .position(|c| *c == '\n')
+                .map_or(chars.len(), |offset| i + offset),
+        ),
+        (Some('/'), Some('*')) => {
+            let mut end = i + 2;
+            while end < chars.len() && !(chars[end] == '*' && chars.get(end + 1) == Some(&'/')) {
+                end += 1;
+            }
+            Some((end + 2).min(chars.len()))
+        }
+        _ => None,
+    }
+}
+
+// 去掉 JSONC 中的注释与尾随逗号（tsconfig.json 等配置文件允许这些写法）
+pub fn strip_json_comments(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut result = String::with_capacity(json.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(end) = skip_comment(&chars, i) {
+            i = end;
+            continue;
+        }
+        match chars[i] {
+            '"' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(chars.len());
+                result.extend(&chars[start..i]);
+                continue;
+            }
+            ',' => {
+                // 跳过逗号后的空白和注释，看下一个有效字符是否为 } 或 ]
+                let mut j = i + 1;
+                while j < chars.len() {
+                    if chars[j].is_whitespace() {
+                        j += 1;
+                    } else if let Some(end) = skip_comment(&chars, j) {
+                        j = end;
+                    } else {
+                        break;
+                    }
+                }
+                if matches!(chars.get(j), Some('}' | ']')) {
+                    i += 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        result.push(chars[i]);
+        i += 1;
+    }
+    result
 }
 
 pub fn get_project_root_path() -> Result<String, io::Error> {
+    // 子命令之后的参数为项目根目录
+    let root_arg = match get_command() {
+        Command::Restore => env::args().nth(2),
+        Command::Analyze => env::args().nth(1),
+    };
     // 优先取命令行参数
-    if let Some(arg1) = env::args().nth(1) {
+    if let Some(arg1) = root_arg {
         Ok(arg1)
     } else {
         // 没有参数则用当前目录
@@ -98,14 +414,82 @@ pub fn has_file_extension(file_path: &str) -> bool {
     }
 }
 
-pub fn multi_pattern_filter(files: &[String], patterns: &[String]) -> Vec<String> {
+// 按 ignoreDependencies 中的 glob 过滤依赖，同时返回没有匹配到任何已声明依赖的 pattern
+pub fn filter_ignored_dependencies(
+    dependencies: &HashSet<String>,
+    patterns: &[String],
+) -> (HashSet<String>, Vec<String>) {
+    let matchers: Vec<_> = patterns
+        .iter()
+        .map(|pattern| (pattern, Pattern::new(pattern).ok()))
+        .collect();
+    let is_match = |pattern: &String, matcher: &Option<Pattern>, dep: &str| match matcher {
+        Some(matcher) => matcher.matches(dep),
+        None => pattern == dep,
+    };
+
+    let kept = dependencies
+        .iter()
+        .filter(|dep| {
+            !matchers
+                .iter()
+                .any(|(pattern, matcher)| is_match(pattern, matcher, dep))
+        })
+        .cloned()
+        .collect();
+    let unmatched = matchers
+        .iter()
+        .filter(|(pattern, matcher)| {
+            !dependencies
+                .iter()
+                .any(|dep| is_match(pattern, matcher, dep))
+        })
+        .map(|(pattern, _)| pattern.to_string())
+        .collect();
+    (kept, unmatched)
+}
+
+pub fn build_gitignore(patterns: &[String]) -> Gitignore {
     // 创建忽略规则构建器（当前目录为根）
     let mut builder = GitignoreBuilder::new("");
     // 添加所有 pattern
     for pat in patterns {
         builder.add_line(None, pat).unwrap();
     }
-    let gitignore = builder.build().unwrap();
+    builder.build().unwrap()
+}
+
+// 在项目根目录下执行 glob，返回相对于项目根目录、以 / 分隔的文件路径
+pub fn glob_in_project(project_root: &str, pattern: &str) -> Vec<String> {
+    let root = clean(project_root);
+    let full_pattern = format!("{}/{}", Pattern::escape(&root.to_string_lossy()), pattern);
+    glob(&full_pattern)
+        .expect("Failed to read glob pattern")
+        .filter_map(|entry| match entry {
+            Ok(path) => Some(path),
+            Err(e) => {
+                println!("{:?}", e);
+                None
+            }
+        })
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            // 根目录为 "." 时 glob 返回的路径不带前缀
+            let relative_path = path.strip_prefix(&root).unwrap_or(&path).to_str()?;
+            Some(relative_path.replace('\\', "/"))
+        })
+        .collect()
+}
+
+pub fn has_ignored_ext(file_path: &str, ignored_exts: &HashSet<String>) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ignored_exts.contains(&ext.to_lowercase()))
+}
+
+pub fn multi_pattern_filter(files: &[String], patterns: &[String]) -> Vec<String> {
+    let gitignore = build_gitignore(patterns);
 
     files
         .iter()
@@ -120,13 +504,7 @@ pub fn multi_pattern_filter(files: &[String], patterns: &[String]) -> Vec<String
 }
 
 pub fn is_path_ignored(file: &str, patterns: &[String]) -> bool {
-    let mut builder = GitignoreBuilder::new("");
-    for pattern in patterns {
-        builder.add_line(None, pattern).unwrap();
-    }
-    let gitignore = builder.build().unwrap();
-
-    gitignore
+    build_gitignore(patterns)
         .matched_path_or_any_parents(Path::new(file), false)
         .is_ignore()
 }
@@ -137,12 +515,40 @@ mod tests {
 
     const USER_ROOT_PATH: &str = "/Users/neo/Desktop/neo/github/minip";
 
+    #[test]
+    fn test_parse_command() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let no_dir = |_: &str| false;
+        let restore_dir = |path: &str| path == RESTORE_COMMAND;
+        assert_eq!(parse_command(&args(&[]), no_dir), Command::Analyze);
+        assert_eq!(parse_command(&args(&["../app"]), no_dir), Command::Analyze);
+        assert_eq!(parse_command(&args(&["restore"]), no_dir), Command::Restore);
+        assert_eq!(
+            parse_command(&args(&["restore", "../app"]), no_dir),
+            Command::Restore
+        );
+        // 存在名为 restore 的项目目录
+        assert_eq!(
+            parse_command(&args(&["restore"]), restore_dir),
+            Command::Analyze
+        );
+        assert_eq!(
+            parse_command(&args(&["restore", "."]), restore_dir),
+            Command::Restore
+        );
+        assert_eq!(
+            parse_command(&args(&["./restore"]), restore_dir),
+            Command::Analyze
+        );
+    }
+
     #[test]
     fn file_types_should_dot() {
         assert_eq!(
             SUPPORT_FILE_TYPES_WITH_DOT,
             [
-                ".ts", ".tsx", ".less", ".scss", ".css", ".png", ".jpg", ".jpeg", ".gif", ".svg",
+                ".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs", ".vue", ".svelte",
+                ".astro", ".less", ".scss", ".css", ".png", ".jpg", ".jpeg", ".gif", ".svg",
                 ".mp3", ".mp4", ".wav", ".woff", ".woff2", ".ttf", ".eot", ".json",
             ]
         )
@@ -186,14 +592,225 @@ mod tests {
                 ignore_ext: None,
                 ignore_files: Some(vec!["src/index.ts".to_string(), "src/core/**".to_string()]),
                 ignore_dependencies: Some(vec!["@types*".to_string(), "eslint".to_string()]),
+                include: None,
+                entries: None,
+                dev_files: None,
             }
         );
     }
 
+    #[test]
+    fn test_dependency_types() {
+        let project_dependencies: ProjectDependencies = serde_json::from_str(
+            r#"{ "dependencies": { "react": "^19", "vite": "^6" }, "devDependencies": { "vitest": "^3", "vite": "^6" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            project_dependencies.dependency_types(),
+            HashMap::from([
+                ("react".to_string(), DependencyType::Dependencies),
+                ("vite".to_string(), DependencyType::Dependencies),
+                ("vitest".to_string(), DependencyType::DevDependencies),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_peer_and_optional_dependencies() {
+        let project_dependencies: ProjectDependencies = serde_json::from_str(
+            r#"{
+                "dependencies": { "lodash": "^4.17.0" },
+                "devDependencies": { "react": "^19.1.0", "lodash": "^3", "typescript": "^5" },
+                "peerDependencies": { "react": "^18 || ^19", "typescript": ">=4.8" },
+                "optionalDependencies": { "fsevents": "~2.3.0" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            project_dependencies.dependency_types(),
+            HashMap::from([
+                ("lodash".to_string(), DependencyType::Dependencies),
+                ("react".to_string(), DependencyType::PeerDependencies),
+                ("typescript".to_string(), DependencyType::PeerDependencies),
+                ("fsevents".to_string(), DependencyType::OptionalDependencies),
+            ])
+        );
+        assert_eq!(
+            project_dependencies.dependency_categories()["react"],
+            BTreeSet::from([
+                DependencyType::DevDependencies,
+                DependencyType::PeerDependencies
+            ])
+        );
+        // ^19.1.0 在 ^18 || ^19 之内，>=4.8 无法只凭主版本号判断
+        assert_eq!(
+            project_dependencies.version_conflicts(),
+            BTreeMap::from([(
+                "lodash".to_string(),
+                BTreeMap::from([
+                    (DependencyType::Dependencies, "^4.17.0".to_string()),
+                    (DependencyType::DevDependencies, "^3".to_string()),
+                ])
+            )])
+        );
+    }
+
+    #[test]
+    fn test_get_range_majors() {
+        assert_eq!(
+            get_range_majors("^18 || ^19.2.0"),
+            Some(BTreeSet::from([18, 19]))
+        );
+        assert_eq!(get_range_majors("~2.3.0"), Some(BTreeSet::from([2])));
+        assert_eq!(get_range_majors("3.x"), Some(BTreeSet::from([3])));
+        assert_eq!(get_range_majors(">=16"), None);
+        assert_eq!(get_range_majors("1.2.3 - 2.0.0"), None);
+        assert_eq!(get_range_majors("workspace:*"), None);
+        assert_eq!(get_range_majors("*"), None);
+    }
+
+    #[test]
+    fn test_dev_files_matcher() {
+        let matcher = MinippConfig::default().dev_files_matcher();
+        for (path, is_dev) in [
+            ("src/utils.test.ts", true),
+            ("src/__tests__/utils.ts", true),
+            ("src/Button.stories.tsx", true),
+            ("src/utils.ts", false),
+        ] {
+            assert_eq!(
+                matcher.matched_path_or_any_parents(path, false).is_ignore(),
+                is_dev,
+                "{}",
+                path
+            );
+        }
+        let config = MinippConfig {
+            dev_files: Some(vec!["src/mocks/**".to_string()]),
+            ..Default::default()
+        };
+        let matcher = config.dev_files_matcher();
+        assert!(
+            matcher
+                .matched_path_or_any_parents("src/mocks/user.ts", false)
+                .is_ignore()
+        );
+        assert!(
+            !matcher
+                .matched_path_or_any_parents("src/utils.test.ts", false)
+                .is_ignore()
+        );
+    }
+
+    #[test]
+    fn test_ignore_files_matcher() {
+        let config = MinippConfig {
+            ignore_files: Some(vec!["src/index.ts".to_string(), "src/core/**".to_string()]),
+            ..Default::default()
+        };
+        let matcher = config.ignore_files_matcher();
+        assert!(
+            matcher
+                .matched_path_or_any_parents("src/index.ts", false)
+                .is_ignore()
+        );
+        assert!(
+            matcher
+                .matched_path_or_any_parents("src/core/cli/index.ts", false)
+                .is_ignore()
+        );
+        assert!(
+            !matcher
+                .matched_path_or_any_parents("src/utils.ts", false)
+                .is_ignore()
+        );
+    }
+
+    #[test]
+    fn test_filter_ignored_dependencies() {
+        let dependencies =
+            HashSet::from(["@types/node", "@types/react", "eslint", "react"].map(String::from));
+        let patterns = ["@types*", "eslint", "prettier"].map(String::from);
+        let (kept, unmatched) = filter_ignored_dependencies(&dependencies, &patterns);
+        assert_eq!(kept, HashSet::from(["react".to_string()]));
+        assert_eq!(unmatched, vec!["prettier".to_string()]);
+    }
+
+    #[test]
+    fn test_has_ignored_ext() {
+        let config = MinippConfig {
+            ignore_ext: Some(vec![".PNG".to_string(), "woff2".to_string()]),
+            ..Default::default()
+        };
+        let ignored_exts = config.ignored_exts();
+        assert!(has_ignored_ext("src/assets/logo.png", &ignored_exts));
+        assert!(has_ignored_ext("src/fonts/a.woff2", &ignored_exts));
+        assert!(!has_ignored_ext("src/fonts/a.woff", &ignored_exts));
+        assert!(!has_ignored_ext("src/index", &ignored_exts));
+    }
+
+    #[test]
+    fn test_include_matcher() {
+        let default_matcher = MinippConfig::default().include_matcher();
+        assert!(default_matcher.is_included("src/components/Button"));
+        assert!(!default_matcher.is_included("lib/index.ts"));
+
+        let config = MinippConfig {
+            include: Some(
+                ["app/", "./lib", "packages/*/src"]
+                    .map(String::from)
+                    .to_vec(),
+            ),
+            ..Default::default()
+        };
+        let matcher = config.include_matcher();
+        assert_eq!(
+            matcher.glob_patterns("ts"),
+            vec!["app/**/*.ts", "lib/**/*.ts", "packages/*/src/**/*.ts"]
+        );
+        assert!(matcher.is_included("app/routes/home.tsx"));
+        assert!(matcher.is_included("lib/index"));
+        assert!(matcher.is_included("packages/ui/src/button.tsx"));
+        assert!(!matcher.is_included("packages/ui/test/button.tsx"));
+        assert!(!matcher.is_included("src/index.ts"));
+        assert!(!matcher.is_included("react"));
+    }
+
+    #[test]
+    fn test_glob_in_project() {
+        let dir = env::temp_dir().join(format!("minipp-glob-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/pages")).unwrap();
+        fs::write(dir.join("src/index.ts"), "").unwrap();
+        fs::write(dir.join("src/pages/home.ts"), "").unwrap();
+        fs::write(dir.join("src/pages/home.css"), "").unwrap();
+
+        let mut files = glob_in_project(dir.to_str().unwrap(), "src/**/*.ts");
+        files.sort();
+        assert_eq!(files, vec!["src/index.ts", "src/pages/home.ts"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_strip_json_comments() {
+        let json = r#"{
+            // line comment
+            "paths": { "@/*": ["src/*"], }, /* block
+            comment */
+            "url": "http://example.com/a,}",
+            "types": ["node", /* last */ ],
+            "strict": true, // last
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip_json_comments(json)).unwrap();
+        assert_eq!(value["paths"]["@/*"][0], "src/*");
+        assert_eq!(value["url"], "http://example.com/a,}");
+        assert_eq!(value["types"], serde_json::json!(["node"]));
+        assert_eq!(value["strict"], true);
+    }
+
     #[test]
     fn test_has_file_extension() {
-        assert_eq!(has_file_extension("src/main.rs"), false);
-        assert_eq!(has_file_extension("src/main.ts"), true)
+        assert!(!has_file_extension("src/main.rs"));
+        assert!(has_file_extension("src/main.ts"))
     }
 
     #[test]
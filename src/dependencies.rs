@@ -0,0 +1,471 @@
+use crate::common::DependencyType;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+// Node 内置模块，node: 前缀的导入（如 node:test）也视为内置模块
+pub const NODE_BUILTIN_MODULES: [&str; 42] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+pub const NODE_PREFIX: &str = "node:";
+
+pub const TYPES_SCOPE: &str = "@types/";
+
+// 提供全局类型、不需要导入对应运行时包的 @types 包
+pub const GLOBAL_TYPES_PACKAGES: [&str; 5] = [
+    "@types/node",
+    "@types/jest",
+    "@types/mocha",
+    "@types/jasmine",
+    "@types/bun",
+];
+
+// 导入说明符对应的包名：lodash/merge -> lodash，@scope/pkg/sub -> @scope/pkg
+pub fn get_package_name(specifier: &str) -> &str {
+    let mut parts = specifier.splitn(3, '/');
+    let first = parts.next().unwrap_or_default();
+    match (first.starts_with('@'), parts.next()) {
+        (true, Some(name)) => &specifier[..first.len() + 1 + name.len()],
+        _ => first,
+    }
+}
+
+pub fn is_node_builtin(specifier: &str) -> bool {
+    specifier.starts_with(NODE_PREFIX)
+        || NODE_BUILTIN_MODULES.contains(&get_package_name(specifier))
+}
+
+#[derive(Default, Debug, PartialEq, Serialize)]
+pub struct DependencyImports {
+    pub node_builtins: BTreeSet<String>,
+    // package.json imports 中的 # 子路径导入及其指向的文件
+    pub subpath_imports: BTreeMap<String, String>,
+    // 在 package.json 中声明过的包，以及声明它们的字段
+    pub declared: BTreeMap<String, BTreeSet<DependencyType>>,
+    // 没有声明的包，也包括无法解析的别名与 # 子路径导入
+    pub undeclared: BTreeSet<String>,
+}
+
+pub fn classify_dependencies(
+    dependencies: &HashSet<String>,
+    subpath_imports: &HashMap<String, String>,
+    dependency_categories: &BTreeMap<String, BTreeSet<DependencyType>>,
+) -> DependencyImports {
+    let mut dependency_imports = DependencyImports {
+        subpath_imports: subpath_imports
+            .iter()
+            .map(|(specifier, file)| (specifier.to_string(), file.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    for dependency in dependencies {
+        let name = get_package_name(dependency).to_string();
+        // 与内置模块同名的 npm 包（如 buffer、events）声明后，不带 node: 前缀的导入会解析到该包
+        if !dependency.starts_with(NODE_PREFIX)
+            && let Some(categories) = dependency_categories.get(&name)
+        {
+            dependency_imports.declared.insert(name, categories.clone());
+            continue;
+        }
+        if is_node_builtin(dependency) {
+            let name = dependency.strip_prefix(NODE_PREFIX).unwrap_or(dependency);
+            dependency_imports
+                .node_builtins
+                .insert(get_package_name(name).to_string());
+            continue;
+        }
+        dependency_imports.undeclared.insert(name);
+    }
+    dependency_imports
+}
+
+// 源码中导入但没有在 package.json 中声明、只是因为依赖提升才能运行的包（幽灵依赖），以及导入它们的文件
+pub fn get_phantom_dependencies(
+    file_dependencies: &HashMap<String, HashSet<String>>,
+    declared_dependencies: &HashSet<String>,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let mut phantom_dependencies: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (file, dependencies) in file_dependencies {
+        for dependency in dependencies {
+            // 无法解析的 # 子路径导入不是包
+            if is_node_builtin(dependency) || dependency.starts_with('#') {
+                continue;
+            }
+            let name = get_package_name(dependency);
+            if !declared_dependencies.contains(name) {
+                phantom_dependencies
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(file.to_string());
+            }
+        }
+    }
+    phantom_dependencies
+}
+
+// @types 包对应的运行时包：@types/react -> react，@types/babel__core -> @babel/core
+pub fn get_types_runtime_package(name: &str) -> Option<String> {
+    let name = name.strip_prefix(TYPES_SCOPE)?;
+    Some(match name.split_once("__") {
+        Some((scope, name)) => format!("@{}/{}", scope, name),
+        None => name.to_string(),
+    })
+}
+
+// 运行时包对应的 @types 包：@babel/core -> @types/babel__core
+pub fn get_runtime_types_package(name: &str) -> String {
+    match name.strip_prefix('@').and_then(|name| name.split_once('/')) {
+        Some((scope, name)) => format!("{}{}__{}", TYPES_SCOPE, scope, name),
+        None => format!("{}{}", TYPES_SCOPE, name),
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Serialize)]
+pub struct TypesDependencies {
+    // 运行时包被使用的 @types 包，以及对应的运行时包
+    pub paired: BTreeMap<String, String>,
+    // tsconfig 的 types 中列出的包，以及提供全局类型的 @types 包
+    pub global: BTreeSet<String>,
+    // 对应的运行时包已不在 package.json 中的 @types 包
+    pub orphaned: BTreeSet<String>,
+}
+
+pub fn get_types_dependencies(
+    declared_dependencies: &HashSet<String>,
+    used_dependencies: &HashSet<String>,
+    ts_config_types: &[String],
+) -> TypesDependencies {
+    let mut types_dependencies = TypesDependencies::default();
+    // types 中的 node 指向 @types/node，vitest/globals 指向 vitest
+    for types in ts_config_types {
+        let name = get_package_name(types);
+        for candidate in [name.to_string(), get_runtime_types_package(name)] {
+            if declared_dependencies.contains(&candidate) {
+                types_dependencies.global.insert(candidate);
+            }
+        }
+    }
+    for dependency in declared_dependencies {
+        let Some(runtime) = get_types_runtime_package(dependency) else {
+            continue;
+        };
+        if GLOBAL_TYPES_PACKAGES.contains(&dependency.as_str()) {
+            types_dependencies.global.insert(dependency.to_string());
+        } else if used_dependencies.contains(&runtime) {
+            types_dependencies
+                .paired
+                .insert(dependency.to_string(), runtime);
+        } else if !declared_dependencies.contains(&runtime)
+            && !types_dependencies.global.contains(dependency)
+        {
+            types_dependencies.orphaned.insert(dependency.to_string());
+        }
+    }
+    types_dependencies
+}
+
+#[derive(Default, Debug, PartialEq, Serialize)]
+pub struct MisplacedDependencies {
+    // devDependencies 中被生产代码在运行时导入的包，以及导入它们的生产文件
+    pub dev_dependencies_in_production: BTreeMap<String, BTreeSet<String>>,
+    // dependencies 中只被测试、stories 或配置文件导入的包，以及导入它们的文件
+    pub dependencies_only_in_dev: BTreeMap<String, BTreeSet<String>>,
+}
+
+pub fn get_misplaced_dependencies(
+    file_dependencies: &HashMap<String, HashSet<String>>,
+    file_runtime_dependencies: &HashMap<String, HashSet<String>>,
+    dependency_types: &HashMap<String, DependencyType>,
+    is_dev_file: impl Fn(&str) -> bool,
+) -> MisplacedDependencies {
+    let mut misplaced = MisplacedDependencies::default();
+    for (file, dependencies) in file_runtime_dependencies {
+        if is_dev_file(file) {
+            continue;
+        }
+        for dependency in dependencies {
+            let name = get_package_name(dependency);
+            if dependency_types.get(name) == Some(&DependencyType::DevDependencies) {
+                misplaced
+                    .dev_dependencies_in_production
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(file.to_string());
+            }
+        }
+    }
+
+    let mut importers: HashMap<&str, BTreeSet<String>> = HashMap::new();
+    for (file, dependencies) in file_dependencies {
+        for dependency in dependencies {
+            importers
+                .entry(get_package_name(dependency))
+                .or_default()
+                .insert(file.to_string());
+        }
+    }
+    for (name, files) in importers {
+        if dependency_types.get(name) == Some(&DependencyType::Dependencies)
+            && files.iter().all(|file| is_dev_file(file))
+        {
+            misplaced
+                .dependencies_only_in_dev
+                .insert(name.to_string(), files);
+        }
+    }
+    misplaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_package_name() {
+        assert_eq!(get_package_name("lodash"), "lodash");
+        assert_eq!(get_package_name("lodash/merge"), "lodash");
+        assert_eq!(get_package_name("@scope/pkg"), "@scope/pkg");
+        assert_eq!(get_package_name("@scope/pkg/sub/path"), "@scope/pkg");
+        assert_eq!(get_package_name("@scope"), "@scope");
+    }
+
+    #[test]
+    fn test_types_package_names() {
+        assert_eq!(
+            get_types_runtime_package("@types/react"),
+            Some("react".to_string())
+        );
+        assert_eq!(
+            get_types_runtime_package("@types/babel__core"),
+            Some("@babel/core".to_string())
+        );
+        assert_eq!(get_types_runtime_package("react"), None);
+        assert_eq!(get_runtime_types_package("react"), "@types/react");
+        assert_eq!(
+            get_runtime_types_package("@babel/core"),
+            "@types/babel__core"
+        );
+    }
+
+    #[test]
+    fn test_get_types_dependencies() {
+        let declared = HashSet::from(
+            [
+                "react",
+                "@types/react",
+                "@babel/core",
+                "@types/babel__core",
+                "lodash",
+                "@types/lodash",
+                "@types/express",
+                "@types/node",
+                "@types/google.maps",
+                "vitest",
+            ]
+            .map(String::from),
+        );
+        let used = HashSet::from(["react", "@babel/core"].map(String::from));
+        let ts_config_types = ["google.maps", "vitest/globals"].map(String::from);
+        assert_eq!(
+            get_types_dependencies(&declared, &used, &ts_config_types),
+            TypesDependencies {
+                paired: BTreeMap::from([
+                    ("@types/react".to_string(), "react".to_string()),
+                    ("@types/babel__core".to_string(), "@babel/core".to_string()),
+                ]),
+                global: BTreeSet::from(
+                    ["@types/node", "@types/google.maps", "vitest"].map(String::from)
+                ),
+                // @types/lodash 的运行时包仍在，只是没有被使用
+                orphaned: BTreeSet::from(["@types/express".to_string()]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_phantom_dependencies() {
+        let file_dependencies = HashMap::from([
+            (
+                "src/a.ts".to_string(),
+                HashSet::from(["react", "lodash/merge", "fs"].map(String::from)),
+            ),
+            (
+                "src/b.ts".to_string(),
+                HashSet::from(["lodash", "#missing", "@scope/ui/button"].map(String::from)),
+            ),
+        ]);
+        let declared = HashSet::from(["react".to_string()]);
+        assert_eq!(
+            get_phantom_dependencies(&file_dependencies, &declared),
+            BTreeMap::from([
+                (
+                    "lodash".to_string(),
+                    BTreeSet::from(["src/a.ts", "src/b.ts"].map(String::from))
+                ),
+                (
+                    "@scope/ui".to_string(),
+                    BTreeSet::from(["src/b.ts".to_string()])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_get_misplaced_dependencies() {
+        let file_dependencies = HashMap::from([
+            (
+                "src/main.ts".to_string(),
+                HashSet::from(["react", "lodash/merge", "vite"].map(String::from)),
+            ),
+            (
+                "src/main.test.ts".to_string(),
+                HashSet::from(["vitest", "msw/node", "react"].map(String::from)),
+            ),
+        ]);
+        // vite 在 src/main.ts 中只导入了类型
+        let mut file_runtime_dependencies = file_dependencies.clone();
+        file_runtime_dependencies
+            .get_mut("src/main.ts")
+            .unwrap()
+            .remove("vite");
+        let dependency_types = HashMap::from([
+            ("react".to_string(), DependencyType::Dependencies),
+            ("msw".to_string(), DependencyType::Dependencies),
+            ("lodash".to_string(), DependencyType::DevDependencies),
+            ("vite".to_string(), DependencyType::DevDependencies),
+            ("vitest".to_string(), DependencyType::DevDependencies),
+        ]);
+        assert_eq!(
+            get_misplaced_dependencies(
+                &file_dependencies,
+                &file_runtime_dependencies,
+                &dependency_types,
+                |file| file.contains(".test."),
+            ),
+            MisplacedDependencies {
+                dev_dependencies_in_production: BTreeMap::from([(
+                    "lodash".to_string(),
+                    BTreeSet::from(["src/main.ts".to_string()])
+                )]),
+                dependencies_only_in_dev: BTreeMap::from([(
+                    "msw".to_string(),
+                    BTreeSet::from(["src/main.test.ts".to_string()])
+                )]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_dependencies() {
+        let dependencies = HashSet::from(
+            [
+                "fs",
+                "node:path",
+                "fs/promises",
+                "node:test",
+                "react",
+                "react-dom/client",
+                "@scope/ui/button",
+                "left-pad",
+                "~components/Missing",
+                "buffer",
+                "events",
+                "node:util",
+            ]
+            .map(String::from),
+        );
+        let subpath_imports = HashMap::from([(
+            "#internal/utils".to_string(),
+            "src/internal/utils.ts".to_string(),
+        )]);
+        let declared = BTreeMap::from([
+            (
+                "react".to_string(),
+                BTreeSet::from([
+                    DependencyType::DevDependencies,
+                    DependencyType::PeerDependencies,
+                ]),
+            ),
+            (
+                "react-dom".to_string(),
+                BTreeSet::from([DependencyType::Dependencies]),
+            ),
+            (
+                "@scope/ui".to_string(),
+                BTreeSet::from([DependencyType::OptionalDependencies]),
+            ),
+            (
+                "unused".to_string(),
+                BTreeSet::from([DependencyType::Dependencies]),
+            ),
+            // 与内置模块同名的 npm 包
+            (
+                "buffer".to_string(),
+                BTreeSet::from([DependencyType::Dependencies]),
+            ),
+            (
+                "util".to_string(),
+                BTreeSet::from([DependencyType::Dependencies]),
+            ),
+        ]);
+        let mut expected_declared = declared.clone();
+        expected_declared.remove("unused");
+        // 只通过 node: 前缀导入，仍然是内置模块
+        expected_declared.remove("util");
+        assert_eq!(
+            classify_dependencies(&dependencies, &subpath_imports, &declared),
+            DependencyImports {
+                node_builtins: BTreeSet::from(
+                    ["fs", "path", "test", "events", "util"].map(String::from)
+                ),
+                subpath_imports: BTreeMap::from([(
+                    "#internal/utils".to_string(),
+                    "src/internal/utils.ts".to_string()
+                )]),
+                declared: expected_declared,
+                undeclared: BTreeSet::from(["left-pad", "~components"].map(String::from)),
+            }
+        );
+    }
+}
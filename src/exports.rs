@@ -0,0 +1,619 @@
+use crate::processors::js_like::{
+    ImportCallKind, get_import_call, get_url_specifier, try_to_find_files_without_a_suffix,
+};
+use crate::resolver::Resolver;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use swc_ecma_ast::{
+    CallExpr, Decl, ExportSpecifier, ImportSpecifier, Module, ModuleDecl, ModuleItem, NewExpr,
+    ObjectPatProp, Pat, TsModuleName,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+pub const DEFAULT_EXPORT: &str = "default";
+
+// 导入方实际用到的导出名，命名空间导入与动态导入视为用到全部导出
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsedNames {
+    Names(HashSet<String>),
+    All,
+}
+
+// export { imported as name } from 'specifier'，imported 为 None 表示 export * as name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReExport {
+    pub name: String,
+    pub specifier: String,
+    pub imported: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ModuleExports {
+    pub local_exports: HashSet<String>,
+    pub re_exports: Vec<ReExport>,
+    pub star_re_exports: Vec<String>,
+    pub imports: Vec<(String, UsedNames)>,
+}
+
+fn collect_pat_names(pat: &Pat, names: &mut HashSet<String>) {
+    match pat {
+        Pat::Ident(ident) => {
+            names.insert(ident.id.sym.to_string());
+        }
+        Pat::Array(array) => array
+            .elems
+            .iter()
+            .flatten()
+            .for_each(|elem| collect_pat_names(elem, names)),
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(key_value) => {
+                        collect_pat_names(&key_value.value, names)
+                    }
+                    ObjectPatProp::Assign(assign) => {
+                        names.insert(assign.key.id.sym.to_string());
+                    }
+                    ObjectPatProp::Rest(rest) => collect_pat_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => collect_pat_names(&rest.arg, names),
+        Pat::Assign(assign) => collect_pat_names(&assign.left, names),
+        _ => {}
+    }
+}
+
+fn collect_decl_names(decl: &Decl, names: &mut HashSet<String>) {
+    match decl {
+        Decl::Class(class) => {
+            names.insert(class.ident.sym.to_string());
+        }
+        Decl::Fn(function) => {
+            names.insert(function.ident.sym.to_string());
+        }
+        Decl::Var(var) => var
+            .decls
+            .iter()
+            .for_each(|declarator| collect_pat_names(&declarator.name, names)),
+        Decl::Using(using) => using
+            .decls
+            .iter()
+            .for_each(|declarator| collect_pat_names(&declarator.name, names)),
+        Decl::TsInterface(interface) => {
+            names.insert(interface.id.sym.to_string());
+        }
+        Decl::TsTypeAlias(alias) => {
+            names.insert(alias.id.sym.to_string());
+        }
+        Decl::TsEnum(ts_enum) => {
+            names.insert(ts_enum.id.sym.to_string());
+        }
+        Decl::TsModule(module) => {
+            if let TsModuleName::Ident(ident) = &module.id {
+                names.insert(ident.sym.to_string());
+            }
+        }
+    }
+}
+
+// import('./x') 与 require('./x') 无法静态确定用到了哪些导出，require.resolve 与 import.meta.resolve 只取路径不使用导出
+#[derive(Default)]
+struct ImportCallCollector {
+    imports: Vec<(String, UsedNames)>,
+}
+
+impl Visit for ImportCallCollector {
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        if let Some((kind, specifier)) = get_import_call(node) {
+            let used_names = match kind {
+                ImportCallKind::RequireResolve | ImportCallKind::ImportMetaResolve => {
+                    UsedNames::Names(HashSet::new())
+                }
+                ImportCallKind::Dynamic | ImportCallKind::Require => UsedNames::All,
+            };
+            self.imports.push((specifier, used_names));
+        }
+        node.visit_children_with(self)
+    }
+
+    // new URL 引用的脚本（如 Worker）会被单独加载，相当于入口
+    fn visit_new_expr(&mut self, node: &NewExpr) {
+        if let Some(specifier) = get_url_specifier(node) {
+            self.imports.push((specifier, UsedNames::All));
+        }
+        node.visit_children_with(self)
+    }
+}
+
+impl ModuleExports {
+    pub fn from_module(module: &Module) -> Self {
+        let mut module_exports = Self::default();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(decl) = item else {
+                continue;
+            };
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let mut names = HashSet::new();
+                    let mut used_all = false;
+                    for specifier in &import.specifiers {
+                        match specifier {
+                            ImportSpecifier::Named(named) => {
+                                let name = named
+                                    .imported
+                                    .as_ref()
+                                    .map(|imported| imported.atom().to_string())
+                                    .unwrap_or_else(|| named.local.sym.to_string());
+                                names.insert(name);
+                            }
+                            ImportSpecifier::Default(_) => {
+                                names.insert(DEFAULT_EXPORT.to_string());
+                            }
+                            ImportSpecifier::Namespace(_) => used_all = true,
+                        }
+                    }
+                    let used_names = if used_all {
+                        UsedNames::All
+                    } else {
+                        UsedNames::Names(names)
+                    };
+                    module_exports
+                        .imports
+                        .push((import.src.value.to_string(), used_names));
+                }
+                ModuleDecl::ExportDecl(export) => {
+                    collect_decl_names(&export.decl, &mut module_exports.local_exports)
+                }
+                ModuleDecl::ExportNamed(named) => {
+                    for specifier in &named.specifiers {
+                        let (name, imported) = match specifier {
+                            ExportSpecifier::Named(named_specifier) => {
+                                let orig = named_specifier.orig.atom().to_string();
+                                let name = named_specifier
+                                    .exported
+                                    .as_ref()
+                                    .map(|exported| exported.atom().to_string())
+                                    .unwrap_or_else(|| orig.clone());
+                                (name, Some(orig))
+                            }
+                            ExportSpecifier::Namespace(namespace) => {
+                                (namespace.name.atom().to_string(), None)
+                            }
+                            ExportSpecifier::Default(default) => (
+                                default.exported.sym.to_string(),
+                                Some(DEFAULT_EXPORT.to_string()),
+                            ),
+                        };
+                        match &named.src {
+                            Some(src) => module_exports.re_exports.push(ReExport {
+                                name,
+                                specifier: src.value.to_string(),
+                                imported,
+                            }),
+                            None => {
+                                module_exports.local_exports.insert(name);
+                            }
+                        }
+                    }
+                }
+                ModuleDecl::ExportDefaultDecl(_)
+                | ModuleDecl::ExportDefaultExpr(_)
+                | ModuleDecl::TsExportAssignment(_) => {
+                    module_exports
+                        .local_exports
+                        .insert(DEFAULT_EXPORT.to_string());
+                }
+                ModuleDecl::ExportAll(export_all) => module_exports
+                    .star_re_exports
+                    .push(export_all.src.value.to_string()),
+                _ => {}
+            }
+        }
+
+        let mut import_calls = ImportCallCollector::default();
+        module.visit_with(&mut import_calls);
+        module_exports.imports.extend(import_calls.imports);
+        module_exports
+    }
+
+    // 单文件组件中的多个 <script> 块共同组成一个模块
+    pub fn merge(&mut self, other: Self) {
+        self.local_exports.extend(other.local_exports);
+        self.re_exports.extend(other.re_exports);
+        self.star_re_exports.extend(other.star_re_exports);
+        self.imports.extend(other.imports);
+    }
+
+    pub fn exported_names(&self) -> HashSet<String> {
+        self.local_exports
+            .iter()
+            .cloned()
+            .chain(
+                self.re_exports
+                    .iter()
+                    .map(|re_export| re_export.name.clone()),
+            )
+            .collect()
+    }
+}
+
+// 从 consumers 的导入与 entries 的全部导出出发，沿 export * 与 export { } from 追踪，返回被使用的 (文件, 导出名)
+fn get_used_exports<'a>(
+    module_exports: &HashMap<String, ModuleExports>,
+    file_imports: &HashMap<String, HashMap<String, String>>,
+    consumers: impl IntoIterator<Item = &'a String>,
+    entries: impl IntoIterator<Item = &'a String>,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> HashSet<(String, String)> {
+    let resolve = |from: &str, specifier: &str| -> Option<String> {
+        let path = file_imports.get(from)?.get(specifier)?;
+        let file = try_to_find_files_without_a_suffix(path, all_files, resolver);
+        all_files.contains(&file).then_some(file)
+    };
+
+    // None 表示需要该文件的全部导出
+    let mut stack: Vec<(String, Option<String>)> = entries
+        .into_iter()
+        .map(|entry| (entry.to_string(), None))
+        .collect();
+    for file in consumers {
+        let Some(exports) = module_exports.get(file) else {
+            continue;
+        };
+        for (specifier, used_names) in &exports.imports {
+            let Some(target) = resolve(file, specifier) else {
+                continue;
+            };
+            match used_names {
+                UsedNames::All => stack.push((target, None)),
+                UsedNames::Names(names) => stack.extend(
+                    names
+                        .iter()
+                        .map(|name| (target.clone(), Some(name.clone()))),
+                ),
+            }
+        }
+    }
+
+    let mut visited: HashSet<(String, Option<String>)> = HashSet::new();
+    let mut used_exports: HashSet<(String, String)> = HashSet::new();
+    while let Some((file, name)) = stack.pop() {
+        if !visited.insert((file.clone(), name.clone())) {
+            continue;
+        }
+        let Some(exports) = module_exports.get(&file) else {
+            continue;
+        };
+        let re_export_target = |re_export: &ReExport| {
+            resolve(&file, &re_export.specifier).map(|target| (target, re_export.imported.clone()))
+        };
+        match &name {
+            None => {
+                for exported_name in exports.exported_names() {
+                    used_exports.insert((file.clone(), exported_name));
+                }
+                stack.extend(exports.re_exports.iter().filter_map(re_export_target));
+                stack.extend(
+                    exports
+                        .star_re_exports
+                        .iter()
+                        .filter_map(|specifier| resolve(&file, specifier))
+                        .map(|target| (target, None)),
+                );
+            }
+            Some(name) => {
+                let mut found = exports.local_exports.contains(name);
+                for re_export in exports.re_exports.iter().filter(|r| &r.name == name) {
+                    found = true;
+                    stack.extend(re_export_target(re_export));
+                }
+                if found {
+                    used_exports.insert((file.clone(), name.clone()));
+                } else if name != DEFAULT_EXPORT {
+                    // export * 不会转发 default
+                    stack.extend(
+                        exports
+                            .star_re_exports
+                            .iter()
+                            .filter_map(|specifier| resolve(&file, specifier))
+                            .map(|target| (target, Some(name.clone()))),
+                    );
+                }
+            }
+        }
+    }
+    used_exports
+}
+
+// 返回每个文件中未被使用的导出
+pub fn get_unused_exports(
+    module_exports: &HashMap<String, ModuleExports>,
+    file_imports: &HashMap<String, HashMap<String, String>>,
+    used_files: &HashSet<String>,
+    entries: &HashSet<String>,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> BTreeMap<String, Vec<String>> {
+    let used_exports = get_used_exports(
+        module_exports,
+        file_imports,
+        used_files,
+        entries,
+        all_files,
+        resolver,
+    );
+    used_files
+        .iter()
+        .filter_map(|file| {
+            let exports = module_exports.get(file)?;
+            let mut unused: Vec<String> = exports
+                .exported_names()
+                .into_iter()
+                .filter(|name| !used_exports.contains(&(file.clone(), name.clone())))
+                .collect();
+            if unused.is_empty() {
+                return None;
+            }
+            unused.sort();
+            Some((file.clone(), unused))
+        })
+        .collect()
+}
+
+// 工作区库中没有任何导出被其他包用到的文件。只从库以外的文件与入口出发查找使用，
+// 库内部文件之间的导入不算使用，经过库入口转发的导出仍会被追踪到原文件
+pub fn get_unused_cross_package_files(
+    module_exports: &HashMap<String, ModuleExports>,
+    file_imports: &HashMap<String, HashMap<String, String>>,
+    used_files: &HashSet<String>,
+    entries: &HashSet<String>,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+    is_owned: impl Fn(&str) -> bool,
+) -> BTreeSet<String> {
+    let used_exports = get_used_exports(
+        module_exports,
+        file_imports,
+        used_files.iter().filter(|file| !is_owned(file)),
+        entries.iter().filter(|file| !is_owned(file)),
+        all_files,
+        resolver,
+    );
+    used_files
+        .iter()
+        .filter(|file| is_owned(file))
+        .filter(|file| {
+            let exported_names = module_exports
+                .get(*file)
+                .map(ModuleExports::exported_names)
+                .unwrap_or_default();
+            !exported_names.is_empty()
+                && !exported_names
+                    .into_iter()
+                    .any(|name| used_exports.contains(&((*file).clone(), name)))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::js_like::parse_ts_or_tsx;
+
+    #[test]
+    fn test_from_module() {
+        let module = parse_ts_or_tsx(
+            r#"
+import React, { useState as useLocalState, type FC } from 'react';
+import * as utils from './utils';
+import './polyfill';
+export const { a, b: [c] } = obj;
+export function helper() {}
+export interface Props {}
+export { helper as aliasHelper };
+export { x as y, default as Button } from './button';
+export * as icons from './icons';
+export * from './shared';
+export default App;
+const Page = () => import('./pages/home');
+const config = require('./config');
+const worker = require.resolve('./worker');
+const sharedWorker = new SharedWorker(new URL('./shared-worker.ts', import.meta.url));
+"#,
+        )
+        .unwrap();
+        let exports = ModuleExports::from_module(&module);
+        assert_eq!(
+            exports.local_exports,
+            HashSet::from(
+                ["a", "c", "helper", "Props", "aliasHelper", "default"].map(String::from)
+            )
+        );
+        assert_eq!(
+            exports.exported_names(),
+            HashSet::from(
+                [
+                    "a",
+                    "c",
+                    "helper",
+                    "Props",
+                    "aliasHelper",
+                    "default",
+                    "y",
+                    "Button",
+                    "icons"
+                ]
+                .map(String::from)
+            )
+        );
+        assert_eq!(exports.star_re_exports, vec!["./shared".to_string()]);
+        assert_eq!(
+            exports.imports,
+            vec![
+                (
+                    "react".to_string(),
+                    UsedNames::Names(HashSet::from(
+                        ["default", "useState", "FC"].map(String::from)
+                    ))
+                ),
+                ("./utils".to_string(), UsedNames::All),
+                ("./polyfill".to_string(), UsedNames::Names(HashSet::new())),
+                ("./pages/home".to_string(), UsedNames::All),
+                ("./config".to_string(), UsedNames::All),
+                ("./worker".to_string(), UsedNames::Names(HashSet::new())),
+                ("./shared-worker.ts".to_string(), UsedNames::All),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_unused_exports() {
+        let sources = [
+            (
+                "src/main.ts",
+                "import { format, Button } from './utils'; export const version = 1;",
+            ),
+            (
+                "src/utils/index.ts",
+                "export * from './format'; export { default as Button, Size } from './button';",
+            ),
+            (
+                "src/utils/format.ts",
+                "export const format = 1; export const parse = 2;",
+            ),
+            (
+                "src/utils/button.ts",
+                "export default 1; export const Size = 2; export const Color = 3;",
+            ),
+        ];
+        let module_exports: HashMap<String, ModuleExports> = sources
+            .iter()
+            .map(|(file, code)| {
+                (
+                    file.to_string(),
+                    ModuleExports::from_module(&parse_ts_or_tsx(code).unwrap()),
+                )
+            })
+            .collect();
+        let file_imports: HashMap<String, HashMap<String, String>> = HashMap::from([
+            (
+                "src/main.ts".to_string(),
+                HashMap::from([("./utils".to_string(), "src/utils".to_string())]),
+            ),
+            (
+                "src/utils/index.ts".to_string(),
+                HashMap::from([
+                    ("./format".to_string(), "src/utils/format".to_string()),
+                    ("./button".to_string(), "src/utils/button".to_string()),
+                ]),
+            ),
+        ]);
+        let all_files: HashSet<String> = sources.iter().map(|(file, _)| file.to_string()).collect();
+        let entries = HashSet::from(["src/main.ts".to_string()]);
+
+        let unused = get_unused_exports(
+            &module_exports,
+            &file_imports,
+            &all_files,
+            &entries,
+            &all_files,
+            &Resolver::default(),
+        );
+        assert_eq!(
+            unused,
+            BTreeMap::from([
+                (
+                    "src/utils/button.ts".to_string(),
+                    vec!["Color".to_string(), "Size".to_string()]
+                ),
+                ("src/utils/format.ts".to_string(), vec!["parse".to_string()]),
+                ("src/utils/index.ts".to_string(), vec!["Size".to_string()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_get_unused_cross_package_files() {
+        let sources = [
+            (
+                "apps/web/src/main.ts",
+                "import { Button } from '@our/ui'; export const app = Button;",
+            ),
+            (
+                "packages/ui/src/index.ts",
+                "export { Button } from './button'; export { Card } from './card';",
+            ),
+            (
+                "packages/ui/src/button.ts",
+                "import { cx } from './helpers'; export const Button = cx;",
+            ),
+            (
+                "packages/ui/src/card.ts",
+                "import { cx } from './helpers'; export const Card = cx;",
+            ),
+            // 只被库内部的文件使用
+            ("packages/ui/src/helpers.ts", "export const cx = 1;"),
+        ];
+        let module_exports: HashMap<String, ModuleExports> = sources
+            .iter()
+            .map(|(file, code)| {
+                (
+                    file.to_string(),
+                    ModuleExports::from_module(&parse_ts_or_tsx(code).unwrap()),
+                )
+            })
+            .collect();
+        let file_imports: HashMap<String, HashMap<String, String>> = HashMap::from([
+            (
+                "apps/web/src/main.ts".to_string(),
+                HashMap::from([(
+                    "@our/ui".to_string(),
+                    "packages/ui/src/index.ts".to_string(),
+                )]),
+            ),
+            (
+                "packages/ui/src/index.ts".to_string(),
+                HashMap::from([
+                    ("./button".to_string(), "packages/ui/src/button".to_string()),
+                    ("./card".to_string(), "packages/ui/src/card".to_string()),
+                ]),
+            ),
+            (
+                "packages/ui/src/button.ts".to_string(),
+                HashMap::from([(
+                    "./helpers".to_string(),
+                    "packages/ui/src/helpers".to_string(),
+                )]),
+            ),
+            (
+                "packages/ui/src/card.ts".to_string(),
+                HashMap::from([(
+                    "./helpers".to_string(),
+                    "packages/ui/src/helpers".to_string(),
+                )]),
+            ),
+        ]);
+        let all_files: HashSet<String> = sources.iter().map(|(file, _)| file.to_string()).collect();
+        // 库自身的入口不算使用
+        let entries =
+            HashSet::from(["apps/web/src/main.ts", "packages/ui/src/index.ts"].map(String::from));
+
+        let unused = get_unused_cross_package_files(
+            &module_exports,
+            &file_imports,
+            &all_files,
+            &entries,
+            &all_files,
+            &Resolver::default(),
+            |file| file.starts_with("packages/ui/"),
+        );
+        assert_eq!(
+            unused,
+            BTreeSet::from(
+                ["packages/ui/src/card.ts", "packages/ui/src/helpers.ts"].map(String::from)
+            )
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use crate::common::{MinippConfig, build_gitignore};
+use crate::processors::js_like::try_to_find_files_without_a_suffix;
+use crate::resolver::Resolver;
+use crate::workspace::{Workspace, resolve_package_source};
+use path_clean::clean;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// 没有配置 entries 时，include 目录下的这些文件默认作为入口
+pub const DEFAULT_ENTRY_NAMES: [&str; 2] = ["main", "index"];
+
+// 测试与 stories 文件通常不会被业务代码导入，默认也作为入口
+pub const DEFAULT_ENTRY_PATTERNS: [&str; 4] = ["*.test.*", "*.spec.*", "*.stories.*", "__tests__/"];
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageEntries {
+    main: Option<String>,
+    module: Option<String>,
+    exports: Option<Value>,
+}
+
+fn collect_export_paths(value: &Value, paths: &mut Vec<String>) {
+    match value {
+        Value::String(path) => paths.push(path.to_string()),
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_export_paths(value, paths)),
+        Value::Object(map) => map
+            .values()
+            .for_each(|value| collect_export_paths(value, paths)),
+        _ => {}
+    }
+}
+
+fn get_package_entry_paths(project_root: &str) -> Vec<String> {
+    let package_json_path = Path::new(project_root).join("package.json");
+    let Some(package_entries) = fs::read_to_string(package_json_path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<PackageEntries>(&json).ok())
+    else {
+        return vec![];
+    };
+    let mut paths: Vec<String> = [package_entries.main, package_entries.module]
+        .into_iter()
+        .flatten()
+        .collect();
+    if let Some(exports) = &package_entries.exports {
+        collect_export_paths(exports, &mut paths);
+    }
+    paths
+        .into_iter()
+        // 子路径通配符（./*）无法对应到具体文件
+        .filter(|path| !path.contains('*'))
+        .map(|path| clean(path).to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+fn match_files(all_files: &HashSet<String>, patterns: &[String]) -> HashSet<String> {
+    let matcher = build_gitignore(patterns);
+    all_files
+        .iter()
+        .filter(|file| {
+            matcher
+                .matched_path_or_any_parents(file.as_str(), false)
+                .is_ignore()
+        })
+        .cloned()
+        .collect()
+}
+
+// 入口文件：优先使用配置中的 entries（gitignore 风格），否则使用 package.json 的 main/module/exports、
+// include 目录下的 main/index 文件以及测试文件。ignoreFiles 匹配的文件也作为入口，它们导入的文件不会被误报
+pub fn find_entries(
+    project_root: &str,
+    config: &MinippConfig,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> HashSet<String> {
+    let mut entries = match config
+        .entries
+        .as_ref()
+        .filter(|entries| !entries.is_empty())
+    {
+        Some(entries) => match_files(all_files, entries),
+        None => find_default_entries(project_root, config, all_files, resolver),
+    };
+    entries.extend(match_files(
+        all_files,
+        config.ignore_files.as_deref().unwrap_or_default(),
+    ));
+    entries
+}
+
+fn find_default_entries(
+    project_root: &str,
+    config: &MinippConfig,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> HashSet<String> {
+    let mut entries: HashSet<String> = get_package_entry_paths(project_root)
+        .iter()
+        .map(|path| try_to_find_files_without_a_suffix(path, all_files, resolver))
+        .filter(|file| all_files.contains(file))
+        .collect();
+
+    let include = config.include_matcher();
+    let default_patterns = DEFAULT_ENTRY_PATTERNS.map(String::from);
+    let matcher = build_gitignore(&default_patterns);
+    entries.extend(
+        all_files
+            .iter()
+            .filter(|file| {
+                let path = Path::new(file.as_str());
+                let is_default_name = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| DEFAULT_ENTRY_NAMES.contains(&stem))
+                    && path
+                        .parent()
+                        .and_then(|parent| parent.to_str())
+                        .is_some_and(|parent| include.is_root(parent));
+                is_default_name || matcher.matched_path_or_any_parents(path, false).is_ignore()
+            })
+            .cloned(),
+    );
+    entries
+}
+
+// 工作区包 package.json 的 main/module/exports 入口，指向构建产物时改为对应的源码
+pub fn find_workspace_entries(
+    project_root: &str,
+    workspaces: &[Workspace],
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> HashSet<String> {
+    workspaces
+        .iter()
+        .flat_map(|workspace| {
+            let package_root = Path::new(project_root).join(&workspace.dir);
+            get_package_entry_paths(&package_root.to_string_lossy())
+                .into_iter()
+                .filter_map(|path| {
+                    let path = format!("{}/{}", workspace.dir, path);
+                    resolve_package_source(&workspace.dir, &path, all_files, resolver)
+                })
+        })
+        .collect()
+}
+
+// 从入口出发沿导入关系遍历，返回所有可达的文件
+pub fn get_reachable_files(
+    entries: &HashSet<String>,
+    file_imports: &HashMap<String, HashMap<String, String>>,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> HashSet<String> {
+    let mut reachable: HashSet<String> = entries.clone();
+    let mut stack: Vec<&String> = entries.iter().collect();
+    while let Some(file) = stack.pop() {
+        let Some(imports) = file_imports.get(file) else {
+            continue;
+        };
+        for import in imports.values() {
+            let resolved = try_to_find_files_without_a_suffix(import, all_files, resolver);
+            if let Some(resolved) = all_files.get(&resolved)
+                && reachable.insert(resolved.to_string())
+            {
+                stack.push(resolved);
+            }
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_imports(edges: &[(&str, &[&str])]) -> HashMap<String, HashMap<String, String>> {
+        edges
+            .iter()
+            .map(|(from, imports)| {
+                (
+                    from.to_string(),
+                    imports
+                        .iter()
+                        .map(|import| (import.to_string(), import.to_string()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_get_reachable_files() {
+        let all_files = HashSet::from(
+            [
+                "src/main.tsx",
+                "src/App.tsx",
+                "src/utils/index.ts",
+                "src/dead/a.ts",
+                "src/dead/b.ts",
+            ]
+            .map(String::from),
+        );
+        let file_imports = file_imports(&[
+            ("src/main.tsx", &["src/App"]),
+            ("src/App.tsx", &["src/utils", "src/main"]),
+            // 互相导入但从入口不可达
+            ("src/dead/a.ts", &["src/dead/b"]),
+            ("src/dead/b.ts", &["src/dead/a", "src/utils"]),
+        ]);
+        let entries = HashSet::from(["src/main.tsx".to_string()]);
+        let reachable =
+            get_reachable_files(&entries, &file_imports, &all_files, &Resolver::default());
+        assert_eq!(
+            reachable,
+            HashSet::from(["src/main.tsx", "src/App.tsx", "src/utils/index.ts"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn test_find_entries() {
+        let dir = std::env::temp_dir().join(format!("minipp-entries-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "main": "dist/index.js", "exports": { ".": { "import": "./src/lib.ts" }, "./*": "./src/*.ts" } }"#,
+        )
+        .unwrap();
+        let all_files = HashSet::from(
+            [
+                "src/main.tsx",
+                "src/lib.ts",
+                "src/pages/index.tsx",
+                "src/utils.test.ts",
+                "src/utils.ts",
+            ]
+            .map(String::from),
+        );
+        let root = dir.to_str().unwrap();
+
+        let entries = find_entries(
+            root,
+            &MinippConfig::default(),
+            &all_files,
+            &Resolver::default(),
+        );
+        assert_eq!(
+            entries,
+            HashSet::from(["src/main.tsx", "src/lib.ts", "src/utils.test.ts"].map(String::from))
+        );
+
+        let config = MinippConfig {
+            entries: Some(vec!["src/pages/**".to_string()]),
+            ..Default::default()
+        };
+        let entries = find_entries(root, &config, &all_files, &Resolver::default());
+        assert_eq!(entries, HashSet::from(["src/pages/index.tsx".to_string()]));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_ignored_files_are_entries() {
+        let all_files = HashSet::from(
+            [
+                "src/index.ts",
+                "src/page.astro",
+                "src/generated/api.ts",
+                "src/generated/client.ts",
+                "src/dead.ts",
+            ]
+            .map(String::from),
+        );
+        let config = MinippConfig {
+            entries: Some(vec!["src/main.ts".to_string()]),
+            ignore_files: Some(vec![
+                "src/index.ts".to_string(),
+                "src/generated/api.ts".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let entries = find_entries("", &config, &all_files, &Resolver::default());
+        assert_eq!(
+            entries,
+            HashSet::from(["src/index.ts", "src/generated/api.ts"].map(String::from))
+        );
+        let file_imports = file_imports(&[
+            ("src/index.ts", &["src/page.astro"]),
+            ("src/generated/api.ts", &["src/generated/client"]),
+        ]);
+        let reachable =
+            get_reachable_files(&entries, &file_imports, &all_files, &Resolver::default());
+        assert!(reachable.contains("src/page.astro"));
+        assert!(reachable.contains("src/generated/client.ts"));
+        assert!(!reachable.contains("src/dead.ts"));
+    }
+}
@@ -1,4 +1,13 @@
+pub mod backup;
 pub mod common;
+pub mod dependencies;
+pub mod exports;
+pub mod graph;
 pub mod macros;
 
 pub mod processors;
+pub mod report;
+pub mod resolver;
+pub mod tooling;
+pub mod tsconfig;
+pub mod workspace;
@@ -1,67 +1,317 @@
-use minipp_rs::common::{get_project_dependencies, get_project_root_path, load_user_config};
+use minipp_rs::backup::{move_to_backup, restore_from_backup};
+use minipp_rs::common::{
+    BACK_UP_FOLDER, Command, filter_ignored_dependencies, get_command, get_project_root_path,
+    has_ignored_ext, load_user_config, read_project_dependencies,
+};
+use minipp_rs::exports::{get_unused_cross_package_files, get_unused_exports};
+use minipp_rs::graph::{find_entries, find_workspace_entries, get_reachable_files};
 use minipp_rs::processors::js_like::{get_js_like_import_info, try_to_find_files_without_a_suffix};
 use minipp_rs::processors::style_like::get_style_like_import_info;
+use minipp_rs::report::{
+    DependencyReport, PackageUsage, analyze_dependencies, get_used_package_names,
+};
+use minipp_rs::workspace::{
+    find_workspace, find_workspaces, get_unused_workspaces, get_workspace_include,
+    is_library_package,
+};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use std::time::Instant;
 
 #[derive(Serialize)]
 struct AllImport {
+    entries: HashSet<String>,
     imports: HashSet<String>,
-    dependencies: HashSet<String>,
     unused_imports: HashSet<String>,
-    unused_dependencies: HashSet<String>,
+    unused_exports: BTreeMap<String, Vec<String>>,
+    // 根目录 package.json 的依赖检查结果，工作区模式下只包含不属于任何工作区包的文件
+    #[serde(flatten)]
+    dependency_report: DependencyReport,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    workspaces: BTreeMap<String, WorkspaceReport>,
+    // 没有被任何其他包依赖的工作区库，以及它们的目录
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    unused_workspaces: BTreeMap<String, String>,
+}
+
+// 单个工作区包的检查结果，文件路径仍相对于项目根目录
+#[derive(Serialize)]
+struct WorkspaceReport {
+    dir: String,
+    entries: BTreeSet<String>,
+    unused_imports: BTreeSet<String>,
+    unused_exports: BTreeMap<String, Vec<String>>,
+    // 库中没有任何导出被其他包用到的文件，库内部的导入与库自身的入口不算使用
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    unused_cross_package_exports: BTreeSet<String>,
+    #[serde(flatten)]
+    dependency_report: DependencyReport,
+}
+
+fn print_dependency_warnings(label: &str, report: &DependencyReport) {
+    for name in &report.types_dependencies.orphaned {
+        println!(
+            "警告: {}{} 对应的运行时包已不在 package.json 中",
+            label, name
+        );
+    }
+    for (name, ranges) in &report.version_conflicts {
+        let ranges: Vec<_> = ranges
+            .iter()
+            .map(|(category, range)| format!("{} {}", category.field_name(), range))
+            .collect();
+        println!(
+            "警告: {}{} 在多个字段中的版本范围冲突: {}",
+            label,
+            name,
+            ranges.join(", ")
+        );
+    }
+    for (name, files) in &report.phantom_dependencies {
+        println!(
+            "警告: {}{} 没有在 package.json 中声明，但被 {} 个文件导入",
+            label,
+            name,
+            files.len()
+        );
+    }
+}
+
+fn restore(project_root: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let result = restore_from_backup(project_root)?;
+    for moved_file in &result.conflicts {
+        println!(
+            "冲突: {} 已存在，未恢复（备份文件保留在 {}）",
+            moved_file.from, moved_file.to
+        );
+    }
+    for moved_file in &result.missing {
+        println!("缺失: 备份文件 {} 不存在，无法恢复", moved_file.to);
+    }
+    println!(
+        "已恢复 {} 个文件，{} 个冲突",
+        result.restored.len(),
+        result.conflicts.len()
+    );
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let start = Instant::now();
     let project_root = get_project_root_path()?;
-    let minipp_config = load_user_config(&project_root);
+    if get_command() == Command::Restore {
+        return restore(&project_root);
+    }
+    let mut minipp_config = load_user_config(&project_root);
+    let workspaces = find_workspaces(&project_root);
+    if minipp_config.include.is_none() && !workspaces.is_empty() {
+        minipp_config.include = Some(get_workspace_include(&workspaces));
+    }
     println!("{:?}", minipp_config);
 
-    let (js_import, style_import) = rayon::join(
-        || get_js_like_import_info(),
-        || get_style_like_import_info(),
+    let (mut js_import, style_import) = rayon::join(
+        || get_js_like_import_info(&project_root, &minipp_config, &workspaces),
+        || get_style_like_import_info(&project_root, &minipp_config),
     );
 
+    let ignored_exts = minipp_config.ignored_exts();
     let all_imports: HashSet<_> = js_import
         .imports
         .iter()
-        .map(|imp| try_to_find_files_without_a_suffix(imp, &js_import.all_files))
+        .map(|imp| {
+            try_to_find_files_without_a_suffix(imp, &js_import.all_files, &js_import.resolver)
+        })
         .chain(style_import.imports)
         .collect();
 
+    let mut entries = find_entries(
+        &project_root,
+        &minipp_config,
+        &js_import.all_files,
+        &js_import.resolver,
+    );
+    if minipp_config.entries.is_none() {
+        entries.extend(find_workspace_entries(
+            &project_root,
+            &workspaces,
+            &js_import.all_files,
+            &js_import.resolver,
+        ));
+    }
+    // 找不到任何入口时退回到“被任意文件导入即视为使用”，避免把所有文件都当作未使用
+    let used_files = if entries.is_empty() {
+        println!("警告: 没有找到入口文件，请在 minipp.config.json 中配置 entries");
+        all_imports.clone()
+    } else {
+        get_reachable_files(
+            &entries,
+            &js_import.file_imports,
+            &js_import.all_files,
+            &js_import.resolver,
+        )
+    };
+
+    let ignore_files = minipp_config.ignore_files_matcher();
     let unused_imports: HashSet<_> = js_import
         .all_files
-        .difference(&all_imports)
+        .difference(&used_files)
+        .filter(|file| {
+            !has_ignored_ext(file, &ignored_exts)
+                && !ignore_files
+                    .matched_path_or_any_parents(file.as_str(), false)
+                    .is_ignore()
+        })
         .cloned()
         .collect();
 
-    let project_dependencies = get_project_dependencies(&project_root);
-    let unused_dependencies: HashSet<_> = project_dependencies
+    let unused_exports = get_unused_exports(
+        &js_import.module_exports,
+        &js_import.file_imports,
+        &used_files,
+        &entries,
+        &js_import.all_files,
+        &js_import.resolver,
+    );
+
+    let unused_workspaces =
+        get_unused_workspaces(&project_root, &workspaces, &js_import.file_dependencies);
+    for (name, dir) in &unused_workspaces {
+        println!("警告: 工作区包 {}（{}）没有被任何其他包依赖", name, dir);
+    }
+
+    // 按文件所属的 package.json 拆分依赖，根目录为空字符串
+    let mut usages: HashMap<String, PackageUsage> = std::iter::once(String::new())
+        .chain(workspaces.iter().map(|workspace| workspace.dir.clone()))
+        .map(|dir| {
+            (
+                dir.clone(),
+                PackageUsage {
+                    dir,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+    let owner = |file: &str| find_workspace(&workspaces, file).map_or("", |w| w.dir.as_str());
+    for (file, dependencies) in std::mem::take(&mut js_import.file_dependencies) {
+        if let Some(usage) = usages.get_mut(owner(&file)) {
+            usage.file_dependencies.insert(file, dependencies);
+        }
+    }
+    for (file, dependencies) in std::mem::take(&mut js_import.file_runtime_dependencies) {
+        if let Some(usage) = usages.get_mut(owner(&file)) {
+            usage.file_runtime_dependencies.insert(file, dependencies);
+        }
+    }
+    for (file, subpath_imports) in std::mem::take(&mut js_import.file_subpath_imports) {
+        if let Some(usage) = usages.get_mut(owner(&file)) {
+            usage.subpath_imports.extend(subpath_imports);
+        }
+    }
+    let mut root_usage = usages.remove("").unwrap_or_default();
+    root_usage.used_elsewhere = get_used_package_names(
+        usages
+            .values()
+            .flat_map(|usage| usage.file_dependencies.values()),
+    );
+    let root_declared = read_project_dependencies(&project_root).all_dependencies();
+
+    let ignore_dependencies = minipp_config
+        .ignore_dependencies
+        .as_deref()
+        .unwrap_or_default();
+    let all_declared: HashSet<String> = workspaces
         .iter()
-        .filter(|dep| {
-            !js_import
-                .dependencies
-                .iter()
-                .any(|imp| imp == *dep || imp.starts_with(&format!("{}/", dep)))
+        .flat_map(|workspace| {
+            let package_root = Path::new(&project_root).join(&workspace.dir);
+            read_project_dependencies(&package_root.to_string_lossy()).all_dependencies()
         })
-        .cloned()
+        .chain(root_declared.iter().cloned())
         .collect();
+    let (_, unmatched_patterns) = filter_ignored_dependencies(&all_declared, ignore_dependencies);
+    for pattern in unmatched_patterns {
+        println!(
+            "警告: ignoreDependencies 中的 {} 没有匹配到任何依赖",
+            pattern
+        );
+    }
+
+    let mut workspace_reports = BTreeMap::new();
+    for workspace in &workspaces {
+        let Some(mut usage) = usages.remove(&workspace.dir) else {
+            continue;
+        };
+        usage.inherited = root_declared.clone();
+        let dependency_report = analyze_dependencies(&project_root, &minipp_config, usage);
+        print_dependency_warnings(&format!("[{}] ", workspace.name), &dependency_report);
+        let is_owned = |file: &str| owner(file) == workspace.dir;
+        let unused_cross_package_exports = if is_library_package(&project_root, workspace) {
+            get_unused_cross_package_files(
+                &js_import.module_exports,
+                &js_import.file_imports,
+                &used_files,
+                &entries,
+                &js_import.all_files,
+                &js_import.resolver,
+                is_owned,
+            )
+        } else {
+            BTreeSet::new()
+        };
+        workspace_reports.insert(
+            workspace.name.clone(),
+            WorkspaceReport {
+                dir: workspace.dir.clone(),
+                entries: entries
+                    .iter()
+                    .filter(|file| is_owned(file))
+                    .cloned()
+                    .collect(),
+                unused_imports: unused_imports
+                    .iter()
+                    .filter(|file| is_owned(file))
+                    .cloned()
+                    .collect(),
+                unused_exports: unused_exports
+                    .iter()
+                    .filter(|(file, _)| is_owned(file))
+                    .map(|(file, names)| (file.clone(), names.clone()))
+                    .collect(),
+                unused_cross_package_exports,
+                dependency_report,
+            },
+        );
+    }
+    let dependency_report = analyze_dependencies(&project_root, &minipp_config, root_usage);
+    print_dependency_warnings("", &dependency_report);
 
     let all_import = AllImport {
-        dependencies: js_import.dependencies,
+        entries,
         imports: all_imports,
         unused_imports,
-        unused_dependencies,
+        unused_exports,
+        dependency_report,
+        workspaces: workspace_reports,
+        unused_workspaces,
     };
 
     let report = serde_json::to_string_pretty(&all_import)?;
-    File::create("minipp.report.json")?.write_all(report.as_bytes())?;
+    File::create(Path::new(&project_root).join("minipp.report.json"))?
+        .write_all(report.as_bytes())?;
 
     println!("成功生成 minipp.report.json 文件！");
+
+    if minipp_config.need_del == Some(true) {
+        let moved = move_to_backup(&project_root, &all_import.unused_imports)?;
+        println!(
+            "已将 {} 个未使用的文件移动到 {} 目录",
+            moved.len(),
+            BACK_UP_FOLDER
+        );
+    }
     println!("Time elapsed: {:?}", start.elapsed());
     Ok(())
 }
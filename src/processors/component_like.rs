@@ -0,0 +1,252 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const COMPONENT_FILE_TYPES: [&str; 3] = ["vue", "svelte", "astro"];
+
+// Astro 的 front-matter 与 <script> 默认使用 TS
+const ASTRO_FILE_TYPE: &str = "astro";
+
+// <script> 或 <style> 块，src 为外部文件引用
+#[derive(Default, Debug, PartialEq)]
+pub struct ComponentBlock {
+    pub lang: Option<String>,
+    pub src: Option<String>,
+    pub content: String,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Component {
+    pub scripts: Vec<ComponentBlock>,
+    pub styles: Vec<ComponentBlock>,
+    // 模板中 src、href 等属性引用的资源
+    pub assets: Vec<String>,
+}
+
+pub fn is_component_file(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| COMPONENT_FILE_TYPES.contains(&ext))
+}
+
+fn parse_attrs(attrs: &str) -> HashMap<String, String> {
+    let attr_regex =
+        Regex::new(r#"([\w:@.-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#).unwrap();
+    attr_regex
+        .captures_iter(attrs)
+        .map(|cap| {
+            let value = cap
+                .get(2)
+                .or_else(|| cap.get(3))
+                .or_else(|| cap.get(4))
+                .map_or("", |m| m.as_str());
+            (cap[1].to_lowercase(), value.to_string())
+        })
+        .collect()
+}
+
+// 跳过外部链接、站点绝对路径与锚点
+fn is_local_asset(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains("://")
+        && !path.starts_with(['/', '#'])
+        && !path.starts_with("data:")
+}
+
+// Astro 文件开头 --- 之间的 front-matter，返回其内容与剩余的模板
+fn split_front_matter(code: &str) -> (Option<&str>, &str) {
+    let trimmed = code.trim_start();
+    let Some(rest) = trimmed
+        .strip_prefix("---")
+        .filter(|rest| rest.starts_with(['\n', '\r']))
+    else {
+        return (None, code);
+    };
+    match rest.find("\n---") {
+        Some(end) => (Some(&rest[..end]), &rest[end + "\n---".len()..]),
+        None => (None, code),
+    }
+}
+
+// 拆分 Vue、Svelte、Astro 组件中的 <script>、<style> 块与模板
+pub fn parse_component(file_path: &str, code: &str) -> Component {
+    let is_astro = Path::new(file_path)
+        .extension()
+        .is_some_and(|ext| ext == ASTRO_FILE_TYPE);
+    let comment_regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    let block_regex =
+        Regex::new(r#"(?is)<(script|style)\b([^>]*)>(.*?)</(?:script|style)\s*>"#).unwrap();
+    // 模板中引用资源的属性，动态绑定（:src）的值是表达式，无法静态解析
+    let asset_attr_regex =
+        Regex::new(r#"(?:^|\s)(?:src|href|poster|xlink:href)\s*=\s*(?:"([^"]+)"|'([^']+)')"#)
+            .unwrap();
+    let mut component = Component::default();
+    let (front_matter, code) = if is_astro {
+        split_front_matter(code)
+    } else {
+        (None, code)
+    };
+    if let Some(front_matter) = front_matter {
+        component.scripts.push(ComponentBlock {
+            lang: Some("ts".to_string()),
+            src: None,
+            content: front_matter.to_string(),
+        });
+    }
+    let default_lang = is_astro.then(|| "ts".to_string());
+    let code = comment_regex.replace_all(code, "");
+    for cap in block_regex.captures_iter(&code) {
+        let attrs = parse_attrs(&cap[2]);
+        let block = ComponentBlock {
+            lang: attrs
+                .get("lang")
+                .filter(|lang| !lang.is_empty())
+                .cloned()
+                .or_else(|| default_lang.clone()),
+            src: attrs.get("src").filter(|src| !src.is_empty()).cloned(),
+            content: cap[3].to_string(),
+        };
+        if cap[1].eq_ignore_ascii_case("script") {
+            component.scripts.push(block);
+        } else {
+            component.styles.push(block);
+        }
+    }
+    let markup = block_regex.replace_all(&code, "");
+    component.assets = asset_attr_regex
+        .captures_iter(&markup)
+        .filter_map(|cap| cap.get(1).or_else(|| cap.get(2)))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|path| is_local_asset(path))
+        .collect();
+    component
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vue_component() {
+        let code = r#"
+<template>
+  <!-- <img src="./commented.png"> -->
+  <img src="./assets/logo.png" :src="dynamic" alt="logo">
+  <a href="https://example.com/a.png">link</a>
+  <video poster='@/assets/poster.jpg'></video>
+</template>
+
+<script lang="ts">
+export default { name: 'App' }
+</script>
+
+<script setup lang="ts">
+import HelloWorld from './components/HelloWorld.vue'
+</script>
+
+<style lang="scss" scoped>
+@import './styles/vars.scss';
+</style>
+<style src="./theme.css"></style>
+"#;
+        let component = parse_component("src/App.vue", code);
+        assert_eq!(
+            component.scripts,
+            vec![
+                ComponentBlock {
+                    lang: Some("ts".to_string()),
+                    src: None,
+                    content: "\nexport default { name: 'App' }\n".to_string(),
+                },
+                ComponentBlock {
+                    lang: Some("ts".to_string()),
+                    src: None,
+                    content: "\nimport HelloWorld from './components/HelloWorld.vue'\n".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            component.styles,
+            vec![
+                ComponentBlock {
+                    lang: Some("scss".to_string()),
+                    src: None,
+                    content: "\n@import './styles/vars.scss';\n".to_string(),
+                },
+                ComponentBlock {
+                    lang: None,
+                    src: Some("./theme.css".to_string()),
+                    content: String::new(),
+                },
+            ]
+        );
+        assert_eq!(
+            component.assets,
+            vec!["./assets/logo.png", "@/assets/poster.jpg"]
+        );
+    }
+
+    #[test]
+    fn test_parse_svelte_component() {
+        let code = r#"
+<script context="module" lang="ts">
+export const prerender = true;
+</script>
+<script>
+import Header from './Header.svelte';
+</script>
+
+<Header />
+<img src="./logo.svg" alt="{name}" />
+
+<style>
+h1 { background: url('./bg.png'); }
+</style>
+"#;
+        let component = parse_component("src/routes/Page.svelte", code);
+        assert_eq!(component.scripts.len(), 2);
+        assert_eq!(component.scripts[0].lang, Some("ts".to_string()));
+        assert_eq!(component.scripts[1].lang, None);
+        assert_eq!(component.styles.len(), 1);
+        assert_eq!(component.assets, vec!["./logo.svg"]);
+    }
+
+    #[test]
+    fn test_parse_astro_component() {
+        let code = r#"---
+import Layout from '../layouts/Layout.astro';
+const title = "Home";
+---
+<Layout title={title}>
+  <img src="../assets/hero.webp" />
+</Layout>
+<script>
+import '../scripts/menu';
+</script>
+<style lang="scss">
+@import '../styles/home.scss';
+</style>
+"#;
+        let component = parse_component("src/pages/index.astro", code);
+        assert_eq!(
+            component.scripts,
+            vec![
+                ComponentBlock {
+                    lang: Some("ts".to_string()),
+                    src: None,
+                    content:
+                        "\nimport Layout from '../layouts/Layout.astro';\nconst title = \"Home\";"
+                            .to_string(),
+                },
+                ComponentBlock {
+                    lang: Some("ts".to_string()),
+                    src: None,
+                    content: "\nimport '../scripts/menu';\n".to_string(),
+                },
+            ]
+        );
+        assert_eq!(component.styles[0].lang, Some("scss".to_string()));
+        assert_eq!(component.assets, vec!["../assets/hero.webp"]);
+    }
+}
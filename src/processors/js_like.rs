@@ -1,28 +1,210 @@
-use crate::common::{get_project_root_path, has_file_extension};
-use glob::glob;
+use crate::common::{
+    IncludeMatcher, MinippConfig, get_project_root_path, glob_in_project, has_file_extension,
+    has_ignored_ext,
+};
+use crate::dependencies::get_package_name;
+use crate::exports::{DEFAULT_EXPORT, ModuleExports, UsedNames};
+use crate::processors::component_like::{COMPONENT_FILE_TYPES, is_component_file, parse_component};
+use crate::resolver::Resolver;
+use crate::tsconfig::TsConfig;
+use crate::workspace::{Workspace, find_workspace, resolve_workspace_import};
+use glob::{MatchOptions, Pattern};
 use path_clean::clean;
 use rayon::prelude::*;
-use std::collections::HashSet;
-use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::{fs, io};
 use swc_common::errors::{ColorConfig, Handler};
 use swc_common::input::StringInput;
 use swc_common::sync::Lrc;
 use swc_common::{FileName, SourceMap};
 use swc_ecma_ast::{
-    CallExpr, Callee, EsVersion, ExportAll, Expr, ImportDecl, JSXAttr, JSXExpr, Module, NamedExport,
+    CallExpr, Callee, EsVersion, ExportAll, Expr, ImportDecl, ImportSpecifier, JSXAttr, JSXExpr,
+    MemberProp, MetaPropKind, Module, NamedExport, NewExpr,
 };
 use swc_ecma_ast::{JSXAttrValue, Lit};
-use swc_ecma_parser::{Lexer, Parser, Syntax, TsSyntax};
+use swc_ecma_parser::{EsSyntax, Lexer, Parser, Syntax, TsSyntax};
 use swc_ecma_visit::{Visit, VisitWith};
 
+pub const JS_LIKE_FILE_TYPES: [&str; 8] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+// 组件中可以解析的 <script lang="...">，没有 lang 时按 JS 处理
+const SCRIPT_LANGS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+
 #[derive(Default, Debug)]
 pub struct ImportCollector {
     pub imports: HashSet<String>,
     pub dependencies: HashSet<String>,
+    // 每个文件中 # 子路径导入到项目内文件的映射，按最近的 package.json 中的 imports 解析
+    pub file_subpath_imports: HashMap<String, HashMap<String, String>>,
     pub current_file_path: String,
     pub all_files: HashSet<String>,
+    // 每个文件中导入说明符到项目内路径的映射，用于从入口文件做可达性分析
+    pub file_imports: HashMap<String, HashMap<String, String>>,
+    // 每个文件导入的 npm 包、Node 内置模块等非文件说明符
+    pub file_dependencies: HashMap<String, HashSet<String>>,
+    // 同上，但不包括只有类型导入（import type）的说明符，它们不会出现在运行时
+    pub file_runtime_dependencies: HashMap<String, HashSet<String>>,
+    pub module_exports: HashMap<String, ModuleExports>,
+    pub include: IncludeMatcher,
+    pub ts_config: Arc<TsConfig>,
+    // 所有文件扫描完成后才能创建
+    pub resolver: Resolver,
+    // pnpm-workspace.yaml 或 package.json workspaces 中的工作区包
+    pub workspaces: Vec<Workspace>,
+    // 命中 tsconfig paths/baseUrl 的导入，待所有文件扫描完成后再确定指向哪个文件
+    alias_imports: HashSet<AliasImport>,
+    // import.meta.glob 与 require.context，待所有文件扫描完成后再展开
+    glob_imports: HashSet<GlobImport>,
+    // # 开头的子路径导入，待读取 package.json 后再解析
+    pending_subpath_imports: HashSet<PendingSubpathImport>,
+    // 正在处理的导入是否只导入类型
+    type_only: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct AliasImport {
+    from: String,
+    specifier: String,
+    candidates: Vec<String>,
+    // 延迟解析时 ImportCollector::type_only 已被重置，需要随导入一起保存
+    type_only: bool,
+}
+
+// (导入所在文件, # 说明符, 是否只导入类型)
+type PendingSubpathImport = (String, String, bool);
+
+// 所有路径均已转换为相对于项目根目录的路径
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum GlobPattern {
+    // import.meta.glob('./pages/**/*.tsx')，以 ! 开头的为排除规则
+    Glob {
+        patterns: Vec<String>,
+        negated: Vec<String>,
+    },
+    // require.context('./icons', true, /\.svg$/)，正则匹配相对于 dir 的 ./ 开头路径
+    Context {
+        dir: String,
+        recursive: bool,
+        regex: String,
+    },
+}
+
+type FileMatcher = Box<dyn Fn(&str) -> bool>;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct GlobImport {
+    from: String,
+    pattern: GlobPattern,
+}
+
+impl GlobPattern {
+    // 匹配规则中第一个通配符之前的目录，用于在磁盘上查找资源文件
+    fn base_dir(&self) -> Option<String> {
+        match self {
+            GlobPattern::Glob { patterns, .. } => {
+                let bases: HashSet<String> = patterns
+                    .iter()
+                    .map(|pattern| {
+                        pattern
+                            .split('/')
+                            .take_while(|part| !part.contains(['*', '?', '[', '{']))
+                            .collect::<Vec<_>>()
+                            .join("/")
+                    })
+                    .collect();
+                // 多个规则时取公共前缀过于复杂，只处理基准目录一致的情况
+                match bases.into_iter().collect::<Vec<_>>().as_slice() {
+                    [base] if !base.is_empty() && base != ".." => Some(base.to_string()),
+                    _ => None,
+                }
+            }
+            GlobPattern::Context { dir, .. } => Some(dir.to_string()).filter(|dir| !dir.is_empty()),
+        }
+    }
+
+    fn matcher(&self) -> Option<FileMatcher> {
+        match self {
+            GlobPattern::Glob { patterns, negated } => {
+                let compile = |patterns: &[String]| -> Vec<Pattern> {
+                    patterns
+                        .iter()
+                        .flat_map(|pattern| expand_braces(pattern))
+                        .filter_map(|pattern| Pattern::new(&pattern).ok())
+                        .collect()
+                };
+                let (patterns, negated) = (compile(patterns), compile(negated));
+                let options = MatchOptions {
+                    require_literal_separator: true,
+                    ..Default::default()
+                };
+                Some(Box::new(move |file| {
+                    patterns
+                        .iter()
+                        .any(|pattern| pattern.matches_with(file, options))
+                        && !negated
+                            .iter()
+                            .any(|pattern| pattern.matches_with(file, options))
+                }))
+            }
+            GlobPattern::Context {
+                dir,
+                recursive,
+                regex,
+            } => {
+                let regex = match Regex::new(regex) {
+                    Ok(regex) => regex,
+                    Err(e) => {
+                        println!("警告: 无法解析 require.context 中的正则 {}: {}", regex, e);
+                        return None;
+                    }
+                };
+                let (dir, recursive) = (dir.to_string(), *recursive);
+                Some(Box::new(move |file| {
+                    let Some(relative) = Path::new(file)
+                        .strip_prefix(&dir)
+                        .ok()
+                        .and_then(|path| path.to_str())
+                    else {
+                        return false;
+                    };
+                    (recursive || !relative.contains('/'))
+                        && regex.is_match(&format!("./{}", relative))
+                }))
+            }
+        }
+    }
+}
+
+// glob 不支持 {a,b}，展开为多个规则
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(start) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(end) = pattern[start..].find('}').map(|end| start + end) else {
+        return vec![pattern.to_string()];
+    };
+    pattern[start + 1..end]
+        .split(',')
+        .flat_map(|option| {
+            expand_braces(&format!(
+                "{}{}{}",
+                &pattern[..start],
+                option,
+                &pattern[end + 1..]
+            ))
+        })
+        .collect()
+}
+
+// import.meta.glob 的规则以 / 开头时相对于项目根目录
+fn glob_to_real_path(current_file_path: &str, pattern: &str) -> Option<String> {
+    match pattern.strip_prefix('/') {
+        Some(pattern) => Some(pattern.to_string()),
+        None => path_to_real_path(current_file_path, pattern).ok(),
+    }
 }
 
 impl ImportCollector {
@@ -30,51 +212,398 @@ impl ImportCollector {
         if has_file_extension(path) {
             let real_path = path_to_real_path(&self.current_file_path, path);
             if let Ok(s) = real_path {
-                self.imports.insert(s);
+                self.insert_import(&self.current_file_path.clone(), path, s);
             }
         }
     }
 
+    fn insert_import(&mut self, from: &str, specifier: &str, path: String) {
+        self.file_imports
+            .entry(from.to_string())
+            .or_default()
+            .insert(specifier.to_string(), path.clone());
+        self.imports.insert(path);
+    }
+
     fn common_insert(&mut self, path: &str) {
-        let real_path = path_to_real_path(&self.current_file_path, path);
+        let candidates = self.ts_config.resolve_candidates(path);
+        if !candidates.is_empty() {
+            self.alias_imports.insert(AliasImport {
+                from: self.current_file_path.clone(),
+                specifier: path.to_string(),
+                candidates,
+                type_only: self.type_only,
+            });
+            return;
+        }
+        self.insert_without_alias(&self.current_file_path.clone(), path, self.type_only);
+    }
+
+    fn insert_dependency(&mut self, from: &str, specifier: &str, type_only: bool) {
+        if !type_only {
+            self.file_runtime_dependencies
+                .entry(from.to_string())
+                .or_default()
+                .insert(specifier.to_string());
+        }
+        self.file_dependencies
+            .entry(from.to_string())
+            .or_default()
+            .insert(specifier.to_string());
+        self.dependencies.insert(specifier.to_string());
+    }
+
+    // 相对路径与 @/ 指向项目内的文件，其余为 npm 包、Node 内置模块或 # 子路径导入
+    fn insert_without_alias(&mut self, from: &str, path: &str, type_only: bool) {
+        if path.starts_with('#') {
+            self.pending_subpath_imports
+                .insert((from.to_string(), path.to_string(), type_only));
+            return;
+        }
+        if !is_file_specifier(path) {
+            self.insert_dependency(from, path, type_only);
+            return;
+        }
+        let real_path = path_to_real_path(from, path);
         if let Ok(s) = real_path {
             if s.contains("node_modules") {
                 return;
             }
-            if s.starts_with("src/") {
-                self.imports.insert(s);
-            } else {
-                self.dependencies.insert(s);
+            self.insert_import(from, path, s);
+        }
+    }
+
+    fn resolve_subpath_imports(&mut self) {
+        for (from, specifier, type_only) in std::mem::take(&mut self.pending_subpath_imports) {
+            match self.resolver.resolve_subpath_import(&from, &specifier) {
+                Some((scope, target)) if is_file_specifier(&target) => {
+                    let package_json = Path::new(&scope).join("package.json");
+                    let Ok(path) = path_to_real_path(&package_json.to_string_lossy(), &target)
+                    else {
+                        continue;
+                    };
+                    let file =
+                        try_to_find_files_without_a_suffix(&path, &self.all_files, &self.resolver);
+                    self.file_subpath_imports
+                        .entry(from.clone())
+                        .or_default()
+                        .insert(specifier.clone(), file.clone());
+                    self.insert_import(&from, &specifier, file);
+                }
+                // 映射到 npm 包
+                Some((_, target)) => {
+                    self.insert_dependency(&from, &target, type_only);
+                }
+                None => {
+                    self.insert_dependency(&from, &specifier, type_only);
+                }
+            }
+        }
+    }
+
+    // 工作区包名导入同时指向兄弟包的源码，并仍作为依赖参与 package.json 的检查
+    fn resolve_workspace_imports(&mut self) {
+        let mut workspace_imports = vec![];
+        for (from, dependencies) in &self.file_dependencies {
+            for dependency in dependencies {
+                let name = get_package_name(dependency);
+                let Some(workspace) = self.workspaces.iter().find(|w| w.name == name) else {
+                    continue;
+                };
+                let subpath = dependency[name.len()..].trim_start_matches('/');
+                if let Some(file) =
+                    resolve_workspace_import(workspace, subpath, &self.all_files, &self.resolver)
+                {
+                    workspace_imports.push((from.to_string(), dependency.to_string(), file));
+                }
+            }
+        }
+        for (from, specifier, file) in workspace_imports {
+            self.insert_import(&from, &specifier, file);
+        }
+    }
+
+    // 单文件组件：分别解析每个 <script> 块，模板中引用的资源按 JSX 属性处理
+    fn visit_component(&mut self, code: &str) -> ModuleExports {
+        let component = parse_component(&self.current_file_path, code);
+        let mut module_exports = ModuleExports::default();
+        // 组件编译后默认导出组件本身
+        module_exports
+            .local_exports
+            .insert(DEFAULT_EXPORT.to_string());
+        for script in &component.scripts {
+            if let Some(src) = &script.src {
+                self.common_insert(src);
+                module_exports.imports.push((src.clone(), UsedNames::All));
+            }
+            // 其他语言（如 lang="coffee"）无法解析，跳过
+            if script
+                .lang
+                .as_deref()
+                .is_some_and(|lang| !SCRIPT_LANGS.contains(&lang))
+            {
+                continue;
             }
+            let Some(module) = parse_script_block(script.lang.as_deref(), &script.content) else {
+                println!("警告: 无法解析 {} 中的 <script>", self.current_file_path);
+                continue;
+            };
+            module.visit_with(self);
+            module_exports.merge(ModuleExports::from_module(&module));
+        }
+        for asset in &component.assets {
+            self.jsx_attr_insert(asset);
         }
+        module_exports
     }
 
     fn merge_for_mutex(&mut self, mutex_self: Self) {
         self.dependencies.extend(mutex_self.dependencies);
+        self.file_dependencies.extend(mutex_self.file_dependencies);
+        self.file_runtime_dependencies
+            .extend(mutex_self.file_runtime_dependencies);
         self.imports.extend(mutex_self.imports);
         self.all_files.extend(mutex_self.all_files);
+        self.file_imports.extend(mutex_self.file_imports);
+        self.module_exports.extend(mutex_self.module_exports);
+        self.alias_imports.extend(mutex_self.alias_imports);
+        self.glob_imports.extend(mutex_self.glob_imports);
+        self.pending_subpath_imports
+            .extend(mutex_self.pending_subpath_imports);
+    }
+
+    fn glob_insert(&mut self, node: &CallExpr) {
+        let Some(pattern) = get_glob_pattern(node, &self.current_file_path) else {
+            return;
+        };
+        self.glob_imports.insert(GlobImport {
+            from: self.current_file_path.clone(),
+            pattern,
+        });
+    }
+
+    // 将匹配到的文件视为被导入，并且用到了它们的全部导出
+    fn resolve_glob_imports(&mut self, project_root: &str) {
+        for glob_import in std::mem::take(&mut self.glob_imports) {
+            let Some(matcher) = glob_import.pattern.matcher() else {
+                continue;
+            };
+            // 资源文件不在 all_files 中，需要到磁盘上查找
+            let mut files: Vec<String> = glob_import
+                .pattern
+                .base_dir()
+                .map(|base| {
+                    glob_in_project(project_root, &format!("{}/**/*", Pattern::escape(&base)))
+                })
+                .unwrap_or_default();
+            files.extend(self.all_files.iter().cloned());
+            let matched: HashSet<String> = files
+                .into_iter()
+                .filter(|file| {
+                    file != &glob_import.from
+                        && self.include.is_included(file)
+                        && !has_ignored_ext(file, &self.resolver.ignored_exts)
+                        && matcher(file)
+                })
+                .collect();
+            for file in matched {
+                self.insert_import(&glob_import.from, &file, file.clone());
+                self.module_exports
+                    .entry(glob_import.from.clone())
+                    .or_default()
+                    .imports
+                    .push((file, UsedNames::All));
+            }
+        }
+    }
+
+    // 与 tsc 一致，依次尝试各个候选路径，都不存在时再按普通导入（如 npm 包）处理
+    fn resolve_alias_imports(&mut self, project_root: &str) {
+        for alias in std::mem::take(&mut self.alias_imports) {
+            let resolved = alias.candidates.iter().find(|candidate| {
+                match self.resolver.resolve(candidate, &self.all_files) {
+                    Some(file) if is_js_like_file(&file) => self.all_files.contains(&file),
+                    Some(file) => Path::new(project_root).join(file).is_file(),
+                    None => false,
+                }
+            });
+            match resolved {
+                Some(candidate) => {
+                    self.insert_import(&alias.from, &alias.specifier, candidate.to_string())
+                }
+                None => self.insert_without_alias(&alias.from, &alias.specifier, alias.type_only),
+            }
+        }
     }
 }
 
-impl Visit for ImportCollector {
-    fn visit_call_expr(&mut self, node: &CallExpr) {
-        if let Callee::Import(_i) = &node.callee {
-            for arg in &node.args {
-                let expr = &*arg.expr;
-                if let Expr::Lit(Lit::Str(s)) = expr {
-                    self.common_insert(&s.value);
+#[derive(Debug, PartialEq)]
+pub enum ImportCallKind {
+    Dynamic,
+    Require,
+    RequireResolve,
+    ImportMetaResolve,
+}
+
+// 字符串字面量或不含表达式的模板字符串
+pub fn get_static_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl
+            .quasis
+            .first()
+            .map(|quasi| quasi.cooked.as_ref().unwrap_or(&quasi.raw).to_string()),
+        _ => None,
+    }
+}
+
+fn is_import_meta(expr: &Expr) -> bool {
+    matches!(expr, Expr::MetaProp(meta) if meta.kind == MetaPropKind::ImportMeta)
+}
+
+// 识别 import('x')、require('x')、require.resolve('x') 与 import.meta.resolve('x')
+pub fn get_import_call(node: &CallExpr) -> Option<(ImportCallKind, String)> {
+    let kind = match &node.callee {
+        Callee::Import(_) => ImportCallKind::Dynamic,
+        Callee::Expr(callee) => match &**callee {
+            Expr::Ident(ident) if ident.sym == *"require" => ImportCallKind::Require,
+            Expr::Member(member)
+                if matches!(&*member.obj, Expr::Ident(obj) if obj.sym == *"require")
+                    && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == *"resolve") =>
+            {
+                ImportCallKind::RequireResolve
+            }
+            Expr::Member(member)
+                if is_import_meta(&member.obj)
+                    && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == *"resolve") =>
+            {
+                ImportCallKind::ImportMetaResolve
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let specifier = get_static_string(&node.args.first()?.expr)?;
+    Some((kind, specifier))
+}
+
+fn is_member_call(callee: &Callee, is_obj: impl Fn(&Expr) -> bool, props: &[&str]) -> bool {
+    let Callee::Expr(callee) = callee else {
+        return false;
+    };
+    let Expr::Member(member) = &**callee else {
+        return false;
+    };
+    is_obj(&member.obj)
+        && matches!(&member.prop, MemberProp::Ident(prop) if props.contains(&prop.sym.as_str()))
+}
+
+// 识别 new URL('./x', import.meta.url)，与浏览器一致，不以 . 或 / 开头的路径也相对于当前文件
+pub fn get_url_specifier(node: &NewExpr) -> Option<String> {
+    if !matches!(&*node.callee, Expr::Ident(ident) if ident.sym == *"URL") {
+        return None;
+    }
+    let args = node.args.as_ref()?;
+    let [url, base] = args.as_slice() else {
+        return None;
+    };
+    let is_import_meta_url = matches!(
+        &*base.expr,
+        Expr::Member(member)
+            if is_import_meta(&member.obj)
+                && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == *"url")
+    );
+    let specifier = get_static_string(&url.expr).filter(|_| is_import_meta_url)?;
+    // 带协议的地址（https:、data: 等）与绝对路径不指向项目内的文件
+    if specifier.starts_with('/') || specifier.contains(':') {
+        return None;
+    }
+    if specifier.starts_with('.') {
+        Some(specifier)
+    } else {
+        Some(format!("./{}", specifier))
+    }
+}
+
+// 识别 import.meta.glob(...) 与 require.context(...)
+fn get_glob_pattern(node: &CallExpr, current_file_path: &str) -> Option<GlobPattern> {
+    let is_require = |obj: &Expr| matches!(obj, Expr::Ident(ident) if ident.sym == *"require");
+    if is_member_call(&node.callee, is_import_meta, &["glob", "globEager"]) {
+        let first = &*node.args.first()?.expr;
+        let raw_patterns: Vec<String> = match first {
+            Expr::Array(array) => array
+                .elems
+                .iter()
+                .flatten()
+                .filter_map(|elem| get_static_string(&elem.expr))
+                .collect(),
+            expr => vec![get_static_string(expr)?],
+        };
+        let (mut patterns, mut negated) = (vec![], vec![]);
+        for raw_pattern in raw_patterns {
+            match raw_pattern.strip_prefix('!') {
+                Some(raw_pattern) => {
+                    negated.extend(glob_to_real_path(current_file_path, raw_pattern))
                 }
+                None => patterns.extend(glob_to_real_path(current_file_path, &raw_pattern)),
+            }
+        }
+        return (!patterns.is_empty()).then_some(GlobPattern::Glob { patterns, negated });
+    }
+    if is_member_call(&node.callee, is_require, &["context"]) {
+        let dir = get_static_string(&node.args.first()?.expr)?;
+        // 与 webpack 默认值一致：递归查找，匹配所有文件
+        let recursive = match node.args.get(1).map(|arg| &*arg.expr) {
+            Some(Expr::Lit(Lit::Bool(recursive))) => recursive.value,
+            _ => true,
+        };
+        let regex = match node.args.get(2).map(|arg| &*arg.expr) {
+            Some(Expr::Lit(Lit::Regex(regex))) if regex.flags.contains('i') => {
+                format!("(?i){}", regex.exp)
             }
+            Some(Expr::Lit(Lit::Regex(regex))) => regex.exp.to_string(),
+            _ => String::from(r"^\./.*$"),
+        };
+        return Some(GlobPattern::Context {
+            dir: path_to_real_path(current_file_path, &dir).ok()?,
+            recursive,
+            regex,
+        });
+    }
+    None
+}
+
+impl Visit for ImportCollector {
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        if let Some((_, specifier)) = get_import_call(node) {
+            self.common_insert(&specifier);
+        } else {
+            self.glob_insert(node);
+        }
+        node.visit_children_with(self)
+    }
+    fn visit_new_expr(&mut self, node: &NewExpr) {
+        if let Some(specifier) = get_url_specifier(node) {
+            self.insert_without_alias(&self.current_file_path.clone(), &specifier, false);
         }
         node.visit_children_with(self)
     }
     fn visit_export_all(&mut self, node: &ExportAll) {
+        self.type_only = node.type_only;
         self.common_insert(&node.src.value);
+        self.type_only = false;
         node.visit_children_with(self);
     }
 
     fn visit_import_decl(&mut self, import_node: &ImportDecl) {
+        // import type { A } 与 import { type A, type B } 都只导入类型
+        self.type_only = import_node.type_only
+            || (!import_node.specifiers.is_empty()
+                && import_node.specifiers.iter().all(|specifier| {
+                    matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only)
+                }));
         self.common_insert(&import_node.src.value);
+        self.type_only = false;
         import_node.visit_children_with(self);
     }
 
@@ -102,26 +631,21 @@ impl Visit for ImportCollector {
     }
     fn visit_named_export(&mut self, node: &NamedExport) {
         if let Some(s) = &node.src {
-            self.common_insert(&s.value)
+            self.type_only = node.type_only;
+            self.common_insert(&s.value);
+            self.type_only = false;
         }
         node.visit_children_with(self);
     }
 }
 
-fn parse_ts_or_tsx(code: &str) -> Module {
+// 无法解析时输出诊断信息并返回 None，由调用方跳过该文件
+fn parse_with_syntax(code: &str, syntax: Syntax, file_name: &str) -> Option<Module> {
     let cm: Lrc<SourceMap> = Default::default();
     let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
-    let fm = cm.new_source_file(
-        FileName::Custom("virtual.tsx".into()).into(),
-        code.to_string(),
-    );
+    let fm = cm.new_source_file(FileName::Custom(file_name.into()).into(), code.to_string());
     let lexer = Lexer::new(
-        // We want to parse ecmascript
-        Syntax::Typescript(TsSyntax {
-            tsx: true,
-            decorators: false,
-            ..Default::default()
-        }),
+        syntax,
         // EsVersion defaults to es5
         EsVersion::EsNext,
         StringInput::from(&*fm),
@@ -134,82 +658,173 @@ fn parse_ts_or_tsx(code: &str) -> Module {
         e.into_diagnostic(&handler).emit();
     }
 
-    let module = parser
+    parser
         .parse_module()
         .map_err(|e| {
             // Unrecoverable fatal error occurred
             e.into_diagnostic(&handler).emit()
         })
-        .expect("failed to parser module");
-    module
+        .ok()
+}
+
+pub(crate) fn parse_ts_or_tsx(code: &str) -> Option<Module> {
+    parse_with_syntax(
+        code,
+        Syntax::Typescript(TsSyntax {
+            tsx: true,
+            decorators: false,
+            ..Default::default()
+        }),
+        "virtual.tsx",
+    )
+}
+
+// .mts 与 .cts 不支持 JSX，<T>value 是类型断言
+fn parse_ts(code: &str) -> Option<Module> {
+    parse_with_syntax(
+        code,
+        Syntax::Typescript(TsSyntax {
+            decorators: false,
+            ..Default::default()
+        }),
+        "virtual.ts",
+    )
+}
+
+fn parse_js_or_jsx(code: &str) -> Option<Module> {
+    parse_with_syntax(
+        code,
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        "virtual.jsx",
+    )
+}
+
+// 根据文件后缀选择 TS 或 JS 解析器
+fn parse_js_like_file(file_path: &str, code: &str) -> Option<Module> {
+    match Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("ts" | "tsx") => parse_ts_or_tsx(code),
+        Some("mts" | "cts") => parse_ts(code),
+        _ => parse_js_or_jsx(code),
+    }
+}
+
+fn is_file_specifier(specifier: &str) -> bool {
+    specifier.starts_with('.') || specifier.starts_with('/') || specifier.starts_with("@/")
+}
+
+// 根据组件中 <script lang="..."> 选择解析器
+fn parse_script_block(lang: Option<&str>, code: &str) -> Option<Module> {
+    match lang {
+        Some("ts" | "tsx") => parse_ts_or_tsx(code),
+        _ => parse_js_or_jsx(code),
+    }
+}
+
+fn is_js_like_file(file_path: &str) -> bool {
+    is_component_file(file_path)
+        || Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| JS_LIKE_FILE_TYPES.contains(&ext))
 }
 
 pub fn try_to_find_files_without_a_suffix(
     relative_path_for_project: &str,
     all_files: &HashSet<String>,
+    resolver: &Resolver,
 ) -> String {
-    if has_file_extension(relative_path_for_project) {
-        return relative_path_for_project.into();
-    }
-    let candidates = [
-        format!("{}.ts", relative_path_for_project),
-        format!("{}.tsx", relative_path_for_project),
-        format!("{}/index.ts", relative_path_for_project),
-        format!("{}/index.tsx", relative_path_for_project),
-        format!("{}.d.ts", relative_path_for_project),
-        format!("{}/index.d.ts", relative_path_for_project),
-    ];
-
-    for candidate in &candidates {
-        if all_files.contains(candidate) {
-            return candidate.to_string();
-        }
-    }
-    format!(
-        "{}(Unknown file type, the file does not exist in the scan directory, or is not a TSX, TS or .d.ts file)",
-        relative_path_for_project
-    )
+    resolver
+        .resolve(relative_path_for_project, all_files)
+        .unwrap_or_else(|| {
+            format!(
+                "{}(Unknown file type, the file does not exist in the scan directory, or is not a TS, TSX, JS, JSX or .d.ts file)",
+                relative_path_for_project
+            )
+        })
 }
 
-pub fn get_js_like_import_info() -> ImportCollector {
-    let patterns = ["src/**/*.ts", "src/**/*.tsx"];
+pub fn get_js_like_import_info(
+    project_root: &str,
+    config: &MinippConfig,
+    workspaces: &[Workspace],
+) -> ImportCollector {
+    let ignored_exts = config.ignored_exts();
+    let include = config.include_matcher();
+    let patterns: Vec<_> = JS_LIKE_FILE_TYPES
+        .into_iter()
+        .chain(COMPONENT_FILE_TYPES)
+        .filter(|ext| !ignored_exts.contains(*ext))
+        .flat_map(|ext| include.glob_patterns(ext))
+        .collect();
+    // ignoreFiles 匹配的文件仍然扫描，作为入口参与可达性分析，只是不会被报告为未使用
     // 单步并发：同时完成文件检查和内容读取
-    let file_contents: Vec<(PathBuf, String)> = patterns
+    let file_contents: Vec<(String, String)> = patterns
         .iter()
-        .flat_map(|pattern| {
-            glob(pattern)
-                .expect("Failed to read glob pattern")
-                .filter_map(Result::ok)
-        })
+        .flat_map(|pattern| glob_in_project(project_root, pattern))
         .par_bridge()
-        .filter_map(|path| {
-            // 一步完成文件检查和读取
-            match (path.is_file(), fs::read_to_string(&path)) {
-                (true, Ok(content)) => Some((path, content)),
-                (true, Err(e)) => {
+        .filter_map(
+            |path| match fs::read_to_string(Path::new(project_root).join(&path)) {
+                Ok(content) => Some((path, content)),
+                Err(e) => {
                     println!("读取失败: {:?}", e);
                     None
                 }
-                _ => None,
-            }
-        })
+            },
+        )
         .collect();
 
+    let ts_config = Arc::new(TsConfig::load(project_root));
+    // 带有自己 tsconfig 的工作区包按包内的 paths 与 baseUrl 解析，其余使用根目录的配置
+    let workspace_ts_configs: HashMap<&str, Arc<TsConfig>> = workspaces
+        .iter()
+        .filter_map(|workspace| {
+            let config = TsConfig::load_package(project_root, &workspace.dir)?;
+            Some((workspace.dir.as_str(), Arc::new(config)))
+        })
+        .collect();
     let collector = Mutex::new(ImportCollector::default());
     file_contents.par_iter().for_each(|(path, code)| {
-        let path_str = match path.to_str() {
-            Some(p) => p,
-            None => return,
+        let file_ts_config = find_workspace(workspaces, path)
+            .and_then(|workspace| workspace_ts_configs.get(workspace.dir.as_str()))
+            .unwrap_or(&ts_config);
+        let mut local_collector = ImportCollector {
+            current_file_path: path.to_string(),
+            include: include.clone(),
+            ts_config: file_ts_config.clone(),
+            ..Default::default()
         };
-        let module = parse_ts_or_tsx(code);
-        let mut local_collector = ImportCollector::default();
-        local_collector.current_file_path = path_str.to_string();
-        local_collector.all_files.insert(path_str.to_string());
-        module.visit_with(&mut local_collector);
+        local_collector.all_files.insert(path.to_string());
+        let module_exports = if is_component_file(path) {
+            local_collector.visit_component(code)
+        } else if let Some(module) = parse_js_like_file(path, code) {
+            module.visit_with(&mut local_collector);
+            ModuleExports::from_module(&module)
+        } else {
+            // 导入与导出都无法得知，文件本身仍参与可达性分析
+            println!("警告: 无法解析 {}", path);
+            ModuleExports::default()
+        };
+        local_collector
+            .module_exports
+            .insert(path.to_string(), module_exports);
         let mut global_collector = collector.lock().unwrap();
         global_collector.merge_for_mutex(local_collector);
     });
-    collector.into_inner().unwrap()
+    let mut collector = collector.into_inner().unwrap();
+    collector.resolver =
+        Resolver::new(project_root, &ts_config, &collector.all_files, ignored_exts);
+    collector.resolve_alias_imports(project_root);
+    collector.resolve_glob_imports(project_root);
+    collector.resolve_subpath_imports();
+    collector.workspaces = workspaces.to_vec();
+    collector.resolve_workspace_imports();
+    collector
 }
 
 // 用于产生src/开头的路径、文件或者依赖 后续会将文件夹路径统一还原为文件路径(如果有的话)
@@ -245,7 +860,7 @@ fn path_to_real_path(current_file_path: &str, import_path: &str) -> Result<Strin
         let abs = Path::new(import_path);
         Ok(abs
             .strip_prefix(base)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .map_err(io::Error::other)?
             .to_str()
             .unwrap()
             .to_string())
@@ -262,7 +877,7 @@ mod tests {
     #[test]
     fn test_parse_ts_code() {
         let code = "const a: number = 123;";
-        let module: Module = parse_ts_or_tsx(code);
+        let module: Module = parse_ts_or_tsx(code).unwrap();
         assert!(
             !module.body.is_empty(),
             "TS parse result should not be empty"
@@ -274,7 +889,7 @@ mod tests {
             import React from 'react';
             export const App = () => <div>Hello TSX</div>;
         "#;
-        let module: Module = parse_ts_or_tsx(code);
+        let module: Module = parse_ts_or_tsx(code).unwrap();
         assert!(
             !module.body.is_empty(),
             "TSX parse result should not be empty"
@@ -288,6 +903,30 @@ mod tests {
         assert!(has_export, "Should have at least one export in TSX code");
     }
 
+    #[test]
+    fn test_parse_mts_code() {
+        let code = "const size = <number>getSize(); export const double = size * 2;";
+        assert!(parse_js_like_file("src/size.mts", code).is_some());
+        assert!(parse_js_like_file("src/size.cts", code).is_some());
+    }
+
+    #[test]
+    fn test_parse_invalid_code() {
+        assert!(parse_js_like_file("src/bad.js", "const x = ;").is_none());
+        assert!(parse_js_like_file("src/bad.ts", "export const = 1").is_none());
+    }
+
+    #[test]
+    fn test_parse_jsx_code() {
+        let code = r#"
+            import React from 'react';
+            const { helper } = require('./helper');
+            export const App = () => <div>{helper()}</div>;
+        "#;
+        let module: Module = parse_js_like_file("src/App.jsx", code).unwrap();
+        assert_eq!(module.body.len(), 3);
+    }
+
     #[test]
     fn should_collect_import() {
         let code = r#"
@@ -306,9 +945,11 @@ import fs from 'fs'
 import { styleText } from 'util'
 import { hasFileExtension } from '../common'
         "#;
-        let module = parse_ts_or_tsx(code);
-        let mut import_collector = ImportCollector::default();
-        import_collector.current_file_path = String::from("src/core/cli/index.ts");
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/core/cli/index.ts"),
+            ..Default::default()
+        };
         module.visit_with(&mut import_collector);
         let should_import_res =
             HashSet::from(["src/core/common", "src/core/visitor"].map(String::from));
@@ -316,6 +957,73 @@ import { hasFileExtension } from '../common'
             HashSet::from(["fs", "util", "@swc/core", "path", "glob"].map(String::from));
         assert_eq!(import_collector.imports, should_import_res);
         assert_eq!(import_collector.dependencies, should_dependence_res);
+        assert_eq!(
+            import_collector.file_dependencies["src/core/cli/index.ts"],
+            should_dependence_res
+        );
+        assert_eq!(
+            import_collector.file_runtime_dependencies["src/core/cli/index.ts"],
+            should_dependence_res
+        );
+    }
+
+    #[test]
+    fn should_collect_type_only_dependencies() {
+        let code = r#"
+import type { Options } from 'prettier'
+import { type Plugin, type UserConfig } from 'vite'
+export type { Root } from 'postcss'
+import { type Ref, ref } from 'vue'
+import 'zone.js'
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/index.ts"),
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        assert_eq!(
+            import_collector.file_dependencies["src/index.ts"],
+            HashSet::from(["prettier", "vite", "postcss", "vue", "zone.js"].map(String::from))
+        );
+        assert_eq!(
+            import_collector.file_runtime_dependencies["src/index.ts"],
+            HashSet::from(["vue", "zone.js"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn should_keep_type_only_for_deferred_imports() {
+        let code = r#"
+import type { UserConfig } from 'vite'
+import type { Options } from '#types'
+import { ref } from 'vue'
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/main.ts"),
+            ts_config: Arc::new(TsConfig {
+                base_url: Some(String::new()),
+                ..Default::default()
+            }),
+            all_files: HashSet::from(["src/main.ts".to_string()]),
+            resolver: Resolver {
+                package_imports: Some(serde_json::json!({ "#types": "prettier" })),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        import_collector.resolve_alias_imports("");
+        import_collector.resolve_subpath_imports();
+        assert_eq!(
+            import_collector.file_dependencies["src/main.ts"],
+            HashSet::from(["vite", "prettier", "vue"].map(String::from))
+        );
+        assert_eq!(
+            import_collector.file_runtime_dependencies["src/main.ts"],
+            HashSet::from(["vue".to_string()])
+        );
     }
 
     #[test]
@@ -331,15 +1039,250 @@ import { hasFileExtension } from '../common'
     React.lazy(() => import('./Type19'))
   );
         "#;
-        let module = parse_ts_or_tsx(code);
-        let mut import_collector = ImportCollector::default();
-        import_collector.current_file_path = String::from("src/core/cli/index.ts");
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/core/cli/index.ts"),
+            ..Default::default()
+        };
         module.visit_with(&mut import_collector);
         let should_res =
             HashSet::from(["src/core/cli/Type19", "src/core/cli/Type20"].map(String::from));
         assert_eq!(import_collector.imports, should_res);
     }
 
+    #[test]
+    fn should_collect_require() {
+        let code = r#"
+const fs = require('fs');
+const { helper } = require(`./helper`);
+const workerPath = require.resolve('./worker');
+module.exports = require('lodash/merge');
+const dynamic = require(`./locales/${lang}`);
+        "#;
+        let module = parse_js_or_jsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/index.js"),
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(["src/helper", "src/worker"].map(String::from))
+        );
+        assert_eq!(
+            import_collector.dependencies,
+            HashSet::from(["fs", "lodash/merge"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn should_collect_vue_component_imports() {
+        let code = r#"
+<template>
+  <HelloWorld />
+  <img src="./assets/logo.png" alt="logo">
+</template>
+
+<script setup lang="ts">
+import HelloWorld from './components/HelloWorld.vue'
+import { useStore } from '@/store'
+import { ref } from 'vue'
+</script>
+
+<script>
+export const meta = { title: 'App' }
+</script>
+        "#;
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/App.vue"),
+            ..Default::default()
+        };
+        let module_exports = import_collector.visit_component(code);
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(
+                [
+                    "src/components/HelloWorld.vue",
+                    "src/store",
+                    "src/assets/logo.png",
+                ]
+                .map(String::from)
+            )
+        );
+        assert_eq!(
+            import_collector.dependencies,
+            HashSet::from(["vue".to_string()])
+        );
+        assert_eq!(
+            module_exports.local_exports,
+            HashSet::from(["default", "meta"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn should_skip_unparsable_script_blocks() {
+        let code = r#"
+<script lang="coffee">
+import Foo from './Foo.vue'
+x = -> 1
+</script>
+
+<script>
+const x = ;
+</script>
+
+<script setup lang="ts">
+import { ref } from 'vue'
+</script>
+        "#;
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/App.vue"),
+            ..Default::default()
+        };
+        import_collector.visit_component(code);
+        assert!(import_collector.imports.is_empty());
+        assert_eq!(
+            import_collector.dependencies,
+            HashSet::from(["vue".to_string()])
+        );
+    }
+
+    #[test]
+    fn should_resolve_subpath_imports() {
+        let code = r#"
+import { log } from '#internal/logger'
+import pad from '#pad'
+import missing from '#missing'
+import path from 'node:path'
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/index.ts"),
+            all_files: HashSet::from(["src/index.ts", "src/internal/logger.ts"].map(String::from)),
+            resolver: Resolver {
+                package_imports: Some(serde_json::json!({
+                    "#internal/*": "./src/internal/*.js",
+                    "#pad": "left-pad"
+                })),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        import_collector.resolve_subpath_imports();
+        assert_eq!(
+            import_collector.file_subpath_imports["src/index.ts"],
+            HashMap::from([(
+                "#internal/logger".to_string(),
+                "src/internal/logger.ts".to_string()
+            )])
+        );
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(["src/internal/logger.ts".to_string()])
+        );
+        assert_eq!(
+            import_collector.dependencies,
+            HashSet::from(["left-pad", "#missing", "node:path"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn should_collect_url_imports() {
+        let code = r#"
+const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+const wasm = new URL('../wasm/lib.wasm', import.meta.url);
+const logo = new URL(`logo.png`, import.meta.url).href;
+const remote = new URL('https://example.com/a.png', import.meta.url);
+const other = new URL('./not-relative-to-module.png', location.href);
+const resolved = import.meta.resolve('./locales/en.json');
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/app/index.ts"),
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(
+                [
+                    "src/app/worker.ts",
+                    "src/wasm/lib.wasm",
+                    "src/app/logo.png",
+                    "src/app/locales/en.json",
+                ]
+                .map(String::from)
+            )
+        );
+        assert!(import_collector.dependencies.is_empty());
+    }
+
+    #[test]
+    fn should_expand_glob_imports() {
+        let dir = std::env::temp_dir().join(format!("minipp-glob-imports-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/icons/social")).unwrap();
+        for icon in ["src/icons/home.svg", "src/icons/social/github.svg"] {
+            fs::write(dir.join(icon), "<svg />").unwrap();
+        }
+        let code = r#"
+const pages = import.meta.glob(['./pages/**/*.{tsx,ts}', '!./pages/**/*.test.tsx'], { eager: true });
+const layouts = import.meta.glob('/src/layouts/*.tsx');
+const icons = require.context('./icons', false, /\.SVG$/i);
+const dynamic = import.meta.glob(`./locales/${lang}/*.ts`);
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/router.ts"),
+            all_files: HashSet::from(
+                [
+                    "src/router.ts",
+                    "src/pages/home.tsx",
+                    "src/pages/user/detail.ts",
+                    "src/pages/home.test.tsx",
+                    "src/layouts/default.tsx",
+                    "src/layouts/admin/index.tsx",
+                ]
+                .map(String::from),
+            ),
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        import_collector.resolve_glob_imports(dir.to_str().unwrap());
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(
+                [
+                    "src/pages/home.tsx",
+                    "src/pages/user/detail.ts",
+                    "src/layouts/default.tsx",
+                    "src/icons/home.svg",
+                ]
+                .map(String::from)
+            )
+        );
+        assert_eq!(
+            import_collector.file_imports["src/router.ts"]["src/pages/home.tsx"],
+            "src/pages/home.tsx"
+        );
+        assert!(
+            import_collector.module_exports["src/router.ts"]
+                .imports
+                .contains(&("src/icons/home.svg".to_string(), UsedNames::All))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(
+            expand_braces("src/{a,b}/*.{ts,tsx}"),
+            vec!["src/a/*.ts", "src/a/*.tsx", "src/b/*.ts", "src/b/*.tsx"]
+        );
+        assert_eq!(expand_braces("src/**/*.ts"), vec!["src/**/*.ts"]);
+    }
+
     #[test]
     fn should_collect_assets_import() {
         let code = r#"
@@ -353,14 +1296,94 @@ export default function DomStringSrcTest() {
   );
 }
         "#;
-        let module = parse_ts_or_tsx(code);
-        let mut import_collector = ImportCollector::default();
-        import_collector.current_file_path = String::from("src/index.tsx");
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/index.tsx"),
+            ..Default::default()
+        };
         module.visit_with(&mut import_collector);
         let should_res = HashSet::from(["src/assets/b.jpg", "src/assets/a.jpg"].map(String::from));
         assert_eq!(import_collector.imports, should_res);
     }
 
+    #[test]
+    fn test_try_to_find_files_without_a_suffix() {
+        let all_files = HashSet::from(["src/utils.ts", "src/utils/index.tsx"].map(String::from));
+        let resolver = Resolver::default();
+        assert_eq!(
+            try_to_find_files_without_a_suffix("src/utils", &all_files, &resolver),
+            "src/utils.ts"
+        );
+        let resolver = Resolver {
+            ignored_exts: HashSet::from(["ts".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            try_to_find_files_without_a_suffix("src/utils", &all_files, &resolver),
+            "src/utils/index.tsx"
+        );
+        assert!(
+            try_to_find_files_without_a_suffix("src/missing", &all_files, &resolver)
+                .starts_with("src/missing(Unknown file type")
+        );
+    }
+
+    #[test]
+    fn test_try_to_find_js_files_without_a_suffix() {
+        let all_files =
+            HashSet::from(["src/legacy.mjs", "src/widgets/index.jsx"].map(String::from));
+        let resolver = Resolver::default();
+        assert_eq!(
+            try_to_find_files_without_a_suffix("src/legacy", &all_files, &resolver),
+            "src/legacy.mjs"
+        );
+        assert_eq!(
+            try_to_find_files_without_a_suffix("src/widgets", &all_files, &resolver),
+            "src/widgets/index.jsx"
+        );
+    }
+
+    #[test]
+    fn should_resolve_ts_config_paths() {
+        let code = r#"
+import Button from '~components/Button'
+import { store } from '@app/store'
+import React from 'react'
+        "#;
+        let module = parse_ts_or_tsx(code).unwrap();
+        let mut import_collector = ImportCollector {
+            current_file_path: String::from("src/index.tsx"),
+            ts_config: Arc::new(TsConfig {
+                base_url: Some("src".to_string()),
+                paths: vec![
+                    (
+                        "~components/*".to_string(),
+                        vec!["components/*".to_string()],
+                    ),
+                    (
+                        "@app/*".to_string(),
+                        vec!["app/*".to_string(), "shared/*".to_string()],
+                    ),
+                ],
+                ..Default::default()
+            }),
+            all_files: HashSet::from(
+                ["src/components/Button.tsx", "src/shared/store/index.ts"].map(String::from),
+            ),
+            ..Default::default()
+        };
+        module.visit_with(&mut import_collector);
+        import_collector.resolve_alias_imports("");
+        assert_eq!(
+            import_collector.imports,
+            HashSet::from(["src/components/Button", "src/shared/store"].map(String::from))
+        );
+        assert_eq!(
+            import_collector.dependencies,
+            HashSet::from(["react".to_string()])
+        );
+    }
+
     #[test]
     fn test_path_to_real_path() {
         let current_path = "src/components/CourseForm/index.tsx";
@@ -1,2 +1,3 @@
+pub mod component_like;
 pub mod js_like;
 pub mod style_like;
@@ -1,4 +1,5 @@
-use glob::glob;
+use crate::common::{MinippConfig, glob_in_project};
+use crate::processors::component_like::{COMPONENT_FILE_TYPES, is_component_file, parse_component};
 use path_clean::clean;
 use regex::Regex;
 use std::collections::HashSet;
@@ -23,6 +24,18 @@ impl StyleImportCollector {
         });
         self.imports.extend(imports);
     }
+
+    // 单文件组件中的 <style> 块，src 指向外部样式文件
+    pub fn insert_from_component(&mut self, code: &str) {
+        for style in parse_component(&self.current_file_path, code).styles {
+            if let Some(src) = &style.src
+                && let Ok(path) = path_to_real_path(&self.current_file_path, src)
+            {
+                self.imports.insert(path);
+            }
+            self.insert_from_code(&style.content);
+        }
+    }
 }
 fn get_extract_style_imports(code: &str) -> Vec<String> {
     // 正则表达式和 TS 版本一致
@@ -30,12 +43,13 @@ fn get_extract_style_imports(code: &str) -> Vec<String> {
         r#"@import\s+(?:url\()?['"]?([^'")]+)['"]?\)?|url\(\s*['"]?([^'")]+)['"]?\s*\)"#,
     )
     .unwrap();
+    let absolute_regex = Regex::new(r#"^([a-z]+:)?//"#).unwrap();
     let mut result = Vec::new();
     for cap in regex.captures_iter(code) {
         // 获取匹配到的路径
         let raw_path = cap
             .get(1)
-            .and_then(|m| Some(m.as_str()))
+            .map(|m| m.as_str())
             .or_else(|| cap.get(2).map(|m| m.as_str()));
         let Some(raw_path) = raw_path else { continue };
         // 跳过包含 { $ # 的动态路径
@@ -43,34 +57,47 @@ fn get_extract_style_imports(code: &str) -> Vec<String> {
             continue;
         }
         // 去掉 ? # 后面的部分并去除空白
-        let path = raw_path
-            .split(|c| c == '?' || c == '#')
-            .next()
-            .unwrap()
-            .trim();
+        let path = raw_path.split(['?', '#']).next().unwrap().trim();
         // 排除 http(s)://、//、/ 开头的绝对路径
-        if !Regex::new(r#"^([a-z]+:)?//"#).unwrap().is_match(path) && !path.starts_with('/') {
+        if !absolute_regex.is_match(path) && !path.starts_with('/') {
             result.push(raw_path.to_string());
         }
     }
     result
 }
 
-pub fn get_style_like_import_info() -> StyleImportCollector {
+pub fn get_style_like_import_info(
+    project_root: &str,
+    config: &MinippConfig,
+) -> StyleImportCollector {
     let mut style_import_collector = StyleImportCollector::default();
-    let patterns = ["src/**/*.css", "src/**/*.less", "src/**/*.scss"];
+    let ignored_exts = config.ignored_exts();
+    let include = config.include_matcher();
+    let patterns = ["css", "less", "scss"]
+        .into_iter()
+        .chain(COMPONENT_FILE_TYPES)
+        .filter(|ext| !ignored_exts.contains(*ext))
+        .flat_map(|ext| include.glob_patterns(ext));
+    let ignore_files = config.ignore_files_matcher();
     for pattern in patterns {
-        for entry in glob(pattern).expect("Failed to read glob pattern") {
-            match entry {
-                Ok(path) => {
-                    if path.is_file() {
-                        let code = fs::read_to_string(&path).unwrap();
-                        style_import_collector.current_file_path =
-                            path.to_str().unwrap().to_string();
+        for path in glob_in_project(project_root, &pattern) {
+            if ignore_files
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+            {
+                continue;
+            }
+            match fs::read_to_string(Path::new(project_root).join(&path)) {
+                Ok(code) => {
+                    let is_component = is_component_file(&path);
+                    style_import_collector.current_file_path = path;
+                    if is_component {
+                        style_import_collector.insert_from_component(&code);
+                    } else {
                         style_import_collector.insert_from_code(&code);
                     }
                 }
-                Err(e) => println!("{:?}", e),
+                Err(e) => println!("读取失败: {:?}", e),
             }
         }
     }
@@ -126,7 +153,7 @@ body {
   src: url('fonts/myfont.woff2') format('woff2');
 }
         "#;
-        let should_res: Vec<_> = vec![
+        let should_res: Vec<_> = [
             "images/bg.jpg",
             "icons/icon.svg",
             "reset.css",
@@ -144,6 +171,34 @@ body {
         );
     }
 
+    #[test]
+    fn test_insert_from_component() {
+        let code = r#"
+<template><div class="app"></div></template>
+<style lang="scss" scoped>
+@import './styles/vars.scss';
+.app { background: url('./assets/bg.png'); }
+</style>
+<style src="../theme.css"></style>
+        "#;
+        let mut style_import_collector = StyleImportCollector {
+            current_file_path: "src/views/Home.vue".to_string(),
+            ..Default::default()
+        };
+        style_import_collector.insert_from_component(code);
+        assert_eq!(
+            style_import_collector.imports,
+            HashSet::from(
+                [
+                    "src/views/styles/vars.scss",
+                    "src/views/assets/bg.png",
+                    "src/theme.css",
+                ]
+                .map(String::from)
+            )
+        );
+    }
+
     #[test]
     fn test_path_to_real_path() {
         assert_eq!(
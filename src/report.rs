@@ -0,0 +1,245 @@
+use crate::common::{
+    DependencyType, MinippConfig, filter_ignored_dependencies, read_project_dependencies,
+};
+use crate::dependencies::{
+    DependencyImports, MisplacedDependencies, TypesDependencies, classify_dependencies,
+    get_misplaced_dependencies, get_package_name, get_phantom_dependencies, get_types_dependencies,
+};
+use crate::tooling::{get_config_file_dependencies, get_script_dependencies};
+use crate::tsconfig::TsConfig;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+// 一个 package.json 的依赖检查结果
+#[derive(Default, Debug, Serialize)]
+pub struct DependencyReport {
+    pub dependencies: DependencyImports,
+    // 没有被使用的依赖，以及声明它们的字段
+    pub unused_dependencies: BTreeMap<String, BTreeSet<DependencyType>>,
+    // 只在 package.json scripts 或工具配置文件中用到的依赖，以及用到它们的脚本与配置文件
+    pub tooling_dependencies: BTreeMap<String, BTreeSet<String>>,
+    pub types_dependencies: TypesDependencies,
+    pub phantom_dependencies: BTreeMap<String, BTreeSet<String>>,
+    pub misplaced_dependencies: MisplacedDependencies,
+    // 出现在多个字段中且版本范围冲突的依赖
+    pub version_conflicts: BTreeMap<String, BTreeMap<DependencyType, String>>,
+}
+
+// 属于同一个 package.json 的文件及其导入的依赖，路径均相对于项目根目录
+#[derive(Default, Debug)]
+pub struct PackageUsage {
+    // package.json 所在目录，根目录为空字符串
+    pub dir: String,
+    pub file_dependencies: HashMap<String, HashSet<String>>,
+    pub file_runtime_dependencies: HashMap<String, HashSet<String>>,
+    pub subpath_imports: HashMap<String, String>,
+    // 其他包中用到的依赖：根目录的依赖可以被所有工作区包使用
+    pub used_elsewhere: HashSet<String>,
+    // 上层 package.json 中声明、通过 node_modules 向上查找可以访问到的依赖
+    pub inherited: HashSet<String>,
+}
+
+pub fn analyze_dependencies(
+    project_root: &str,
+    config: &MinippConfig,
+    usage: PackageUsage,
+) -> DependencyReport {
+    let package_root = Path::new(project_root)
+        .join(&usage.dir)
+        .to_string_lossy()
+        .to_string();
+    let package_dependencies = read_project_dependencies(&package_root);
+    let declared_dependencies = package_dependencies.all_dependencies();
+    let dependency_categories = package_dependencies.dependency_categories();
+    let mut file_dependencies = usage.file_dependencies;
+    let imported: HashSet<String> = file_dependencies.values().flatten().cloned().collect();
+    let dependencies =
+        classify_dependencies(&imported, &usage.subpath_imports, &dependency_categories);
+    let ignore_dependencies = config.ignore_dependencies.as_deref().unwrap_or_default();
+    let (project_dependencies, _) =
+        filter_ignored_dependencies(&declared_dependencies, ignore_dependencies);
+
+    let mut tooling_dependencies = get_script_dependencies(&package_root, &declared_dependencies);
+    for (file, config_dependencies) in
+        get_config_file_dependencies(&package_root, &declared_dependencies)
+    {
+        let file = if usage.dir.is_empty() {
+            file
+        } else {
+            format!("{}/{}", usage.dir, file)
+        };
+        for dependency in &config_dependencies {
+            tooling_dependencies
+                .entry(dependency.to_string())
+                .or_default()
+                .insert(file.to_string());
+        }
+        // 配置文件也参与 dependencies 是否只被开发文件使用的判断
+        file_dependencies
+            .entry(file)
+            .or_default()
+            .extend(config_dependencies);
+    }
+    tooling_dependencies.retain(|name, _| !dependencies.declared.contains_key(name));
+
+    let used_dependencies: HashSet<_> = dependencies
+        .declared
+        .keys()
+        .chain(tooling_dependencies.keys())
+        .chain(
+            usage
+                .used_elsewhere
+                .iter()
+                .filter(|name| declared_dependencies.contains(*name)),
+        )
+        .cloned()
+        .collect();
+    let mut types_dependencies = get_types_dependencies(
+        &declared_dependencies,
+        &used_dependencies,
+        &TsConfig::load(&package_root).types,
+    );
+    types_dependencies
+        .orphaned
+        .retain(|name| project_dependencies.contains(name));
+
+    let unused_dependencies: BTreeMap<_, _> = dependency_categories
+        .iter()
+        .filter(|(dep, _)| {
+            project_dependencies.contains(*dep)
+                && !used_dependencies.contains(*dep)
+                && !types_dependencies.paired.contains_key(*dep)
+                && !types_dependencies.global.contains(*dep)
+        })
+        .map(|(dep, categories)| (dep.to_string(), categories.clone()))
+        .collect();
+
+    let mut version_conflicts = package_dependencies.version_conflicts();
+    version_conflicts.retain(|name, _| project_dependencies.contains(name));
+
+    let available_dependencies: HashSet<String> = declared_dependencies
+        .union(&usage.inherited)
+        .cloned()
+        .collect();
+    let mut phantom_dependencies =
+        get_phantom_dependencies(&file_dependencies, &available_dependencies);
+    let (kept_phantom_dependencies, _) = filter_ignored_dependencies(
+        &phantom_dependencies.keys().cloned().collect(),
+        ignore_dependencies,
+    );
+    phantom_dependencies.retain(|name, _| kept_phantom_dependencies.contains(name));
+
+    let dev_files = config.dev_files_matcher();
+    let mut misplaced_dependencies = get_misplaced_dependencies(
+        &file_dependencies,
+        &usage.file_runtime_dependencies,
+        &package_dependencies.dependency_types(),
+        |file| {
+            dev_files
+                .matched_path_or_any_parents(file, false)
+                .is_ignore()
+        },
+    );
+    for misplaced in [
+        &mut misplaced_dependencies.dev_dependencies_in_production,
+        &mut misplaced_dependencies.dependencies_only_in_dev,
+    ] {
+        misplaced.retain(|name, _| project_dependencies.contains(name));
+    }
+
+    DependencyReport {
+        dependencies,
+        unused_dependencies,
+        tooling_dependencies,
+        types_dependencies,
+        phantom_dependencies,
+        misplaced_dependencies,
+        version_conflicts,
+    }
+}
+
+// 用到的包名，用于判断上层 package.json 中的依赖是否被子包使用
+pub fn get_used_package_names<'a>(
+    file_dependencies: impl IntoIterator<Item = &'a HashSet<String>>,
+) -> HashSet<String> {
+    file_dependencies
+        .into_iter()
+        .flatten()
+        .map(|dependency| get_package_name(dependency).to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_analyze_workspace_dependencies() {
+        let dir = std::env::temp_dir().join(format!("minipp-report-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("packages/app")).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "devDependencies": { "typescript": "^5", "vitest": "^3", "prettier": "^3" } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("packages/app/package.json"),
+            r#"{ "dependencies": { "@our/ui": "workspace:*", "react": "^19", "lodash": "^4" } }"#,
+        )
+        .unwrap();
+        let project_root = dir.to_str().unwrap();
+        let app_files = HashMap::from([
+            (
+                "packages/app/src/main.tsx".to_string(),
+                HashSet::from(["@our/ui", "react", "axios"].map(String::from)),
+            ),
+            (
+                "packages/app/src/main.test.tsx".to_string(),
+                HashSet::from(["vitest"].map(String::from)),
+            ),
+        ]);
+        let app = analyze_dependencies(
+            project_root,
+            &MinippConfig::default(),
+            PackageUsage {
+                dir: "packages/app".to_string(),
+                file_dependencies: app_files.clone(),
+                file_runtime_dependencies: app_files.clone(),
+                inherited: HashSet::from(["typescript", "vitest", "prettier"].map(String::from)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            app.unused_dependencies,
+            BTreeMap::from([(
+                "lodash".to_string(),
+                BTreeSet::from([DependencyType::Dependencies])
+            )])
+        );
+        // vitest 在根目录声明，不算幽灵依赖
+        assert_eq!(
+            app.phantom_dependencies,
+            BTreeMap::from([(
+                "axios".to_string(),
+                BTreeSet::from(["packages/app/src/main.tsx".to_string()])
+            )])
+        );
+
+        let root = analyze_dependencies(
+            project_root,
+            &MinippConfig::default(),
+            PackageUsage {
+                used_elsewhere: get_used_package_names(app_files.values()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            root.unused_dependencies.keys().collect::<Vec<_>>(),
+            vec!["prettier", "typescript"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,423 @@
+use crate::common::{has_file_extension, has_ignored_ext};
+use crate::tsconfig::TsConfig;
+use path_clean::clean;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// 对应 tsconfig 中的 moduleResolution，classic 已被 TS 废弃，按 node10 处理
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum ModuleResolution {
+    Node10,
+    Node16,
+    NodeNext,
+    #[default]
+    Bundler,
+}
+
+impl ModuleResolution {
+    pub fn from_config(value: Option<&str>) -> Self {
+        match value.map(|value| value.to_lowercase()).as_deref() {
+            Some("node" | "node10" | "classic") => ModuleResolution::Node10,
+            Some("node16") => ModuleResolution::Node16,
+            Some("nodenext") => ModuleResolution::NodeNext,
+            _ => ModuleResolution::Bundler,
+        }
+    }
+
+    // node10 不支持 package.json 的 exports
+    fn conditions(self) -> Option<&'static [&'static str]> {
+        match self {
+            ModuleResolution::Node10 => None,
+            ModuleResolution::Node16 | ModuleResolution::NodeNext => {
+                Some(&["types", "node", "import", "require", "default"])
+            }
+            ModuleResolution::Bundler => Some(&["types", "import", "require", "default"]),
+        }
+    }
+}
+
+// 与 tsc 一致的扩展名优先级
+const SOURCE_EXTENSIONS: [&str; 9] = [
+    ".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mts", ".cts", ".mjs", ".cjs",
+];
+
+// TS 的 ESM 写法：导入 .js 实际指向同名的 .ts 源文件
+const JS_TO_TS_EXTENSIONS: [(&str, &[&str]); 4] = [
+    (".js", &[".ts", ".tsx", ".d.ts"]),
+    (".jsx", &[".tsx"]),
+    (".mjs", &[".mts", ".d.mts"]),
+    (".cjs", &[".cts", ".d.cts"]),
+];
+
+#[derive(Default, Debug, Deserialize)]
+pub struct PackageJson {
+    pub main: Option<String>,
+    pub types: Option<String>,
+    pub typings: Option<String>,
+    pub exports: Option<Value>,
+    pub imports: Option<Value>,
+}
+
+// 所有路径均为相对于项目根目录、以 / 分隔的路径
+#[derive(Default, Debug)]
+pub struct Resolver {
+    pub module_resolution: ModuleResolution,
+    // 为空时等同于 [""]
+    pub module_suffixes: Vec<String>,
+    pub ignored_exts: HashSet<String>,
+    // 项目内带有 package.json 的目录，不包括根目录
+    pub packages: HashMap<String, PackageJson>,
+    // 项目根目录 package.json 的 imports 字段
+    pub package_imports: Option<Value>,
+}
+
+fn join_relative(base: &str, path: &str) -> String {
+    let path = clean(Path::new(base).join(path))
+        .to_string_lossy()
+        .replace('\\', "/");
+    if path == "." { String::new() } else { path }
+}
+
+// 按条件顺序取出 exports 中 "." 对应的路径
+fn resolve_exports_entry(exports: &Value, conditions: &[&str]) -> Option<String> {
+    match exports {
+        Value::String(path) => Some(path.to_string()),
+        Value::Array(values) => values
+            .iter()
+            .find_map(|value| resolve_exports_entry(value, conditions)),
+        Value::Object(map) => {
+            if map.keys().any(|key| key.starts_with('.')) {
+                return resolve_exports_entry(map.get(".")?, conditions);
+            }
+            conditions
+                .iter()
+                .filter_map(|condition| map.get(*condition))
+                .find_map(|value| resolve_exports_entry(value, conditions))
+        }
+        _ => None,
+    }
+}
+
+impl Resolver {
+    pub fn new(
+        project_root: &str,
+        ts_config: &TsConfig,
+        all_files: &HashSet<String>,
+        ignored_exts: HashSet<String>,
+    ) -> Self {
+        let dirs: HashSet<&str> = all_files
+            .iter()
+            .flat_map(|file| Path::new(file).ancestors().skip(1))
+            .filter_map(|dir| dir.to_str())
+            .filter(|dir| !dir.is_empty())
+            .collect();
+        let packages = dirs
+            .into_iter()
+            .filter_map(|dir| {
+                let json =
+                    fs::read_to_string(Path::new(project_root).join(dir).join("package.json"))
+                        .ok()?;
+                let package_json = serde_json::from_str::<PackageJson>(&json).ok()?;
+                Some((dir.to_string(), package_json))
+            })
+            .collect();
+        let package_imports = fs::read_to_string(Path::new(project_root).join("package.json"))
+            .ok()
+            .and_then(|json| serde_json::from_str::<PackageJson>(&json).ok())
+            .and_then(|package_json| package_json.imports);
+        Self {
+            module_resolution: ModuleResolution::from_config(
+                ts_config.module_resolution.as_deref(),
+            ),
+            module_suffixes: ts_config.module_suffixes.clone(),
+            ignored_exts,
+            packages,
+            package_imports,
+        }
+    }
+
+    // 距离文件最近的 package.json 所在目录，根目录为空字符串
+    pub fn package_scope(&self, file: &str) -> &str {
+        Path::new(file)
+            .ancestors()
+            .skip(1)
+            .filter_map(|dir| dir.to_str())
+            .find_map(|dir| {
+                self.packages
+                    .get_key_value(dir)
+                    .map(|(dir, _)| dir.as_str())
+            })
+            .unwrap_or_default()
+    }
+
+    // # 开头的子路径导入：与 Node 一致，使用距离导入所在文件最近的 package.json 中的 imports。
+    // 精确匹配优先，其次取前缀最长的通配符匹配，返回该 package.json 所在目录与其中的原始目标
+    pub fn resolve_subpath_import(&self, from: &str, specifier: &str) -> Option<(String, String)> {
+        let scope = self.package_scope(from);
+        let imports = if scope.is_empty() {
+            self.package_imports.as_ref()
+        } else {
+            self.packages[scope].imports.as_ref()
+        };
+        let Value::Object(map) = imports? else {
+            return None;
+        };
+        let (target, matched) = match map.get(specifier) {
+            Some(target) => (target, ""),
+            None => map
+                .iter()
+                .filter_map(|(pattern, target)| {
+                    let (prefix, suffix) = pattern.split_once('*')?;
+                    let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                    Some((prefix.len(), target, matched))
+                })
+                .max_by_key(|(prefix_len, _, _)| *prefix_len)
+                .map(|(_, target, matched)| (target, matched))?,
+        };
+        // node10 不支持 imports，但运行时（Node、打包工具）支持，按 bundler 的条件处理
+        let conditions = self
+            .module_resolution
+            .conditions()
+            .or(ModuleResolution::Bundler.conditions())?;
+        resolve_exports_entry(target, conditions)
+            .map(|target| (scope.to_string(), target.replace('*', matched)))
+    }
+
+    fn exists(&self, file: &str, all_files: &HashSet<String>) -> bool {
+        all_files.contains(file) && !has_ignored_ext(file, &self.ignored_exts)
+    }
+
+    fn suffixes(&self) -> Vec<&str> {
+        if self.module_suffixes.is_empty() {
+            vec![""]
+        } else {
+            self.module_suffixes.iter().map(String::as_str).collect()
+        }
+    }
+
+    // 依次尝试 moduleSuffixes 与各扩展名
+    fn try_extensions(
+        &self,
+        base: &str,
+        extensions: &[&str],
+        all_files: &HashSet<String>,
+    ) -> Option<String> {
+        let suffixes = self.suffixes();
+        extensions
+            .iter()
+            .flat_map(|ext| {
+                suffixes
+                    .iter()
+                    .map(move |suffix| format!("{}{}{}", base, suffix, ext))
+            })
+            .find(|candidate| self.exists(candidate, all_files))
+    }
+
+    fn resolve_file(&self, path: &str, all_files: &HashSet<String>) -> Option<String> {
+        for (js_ext, ts_exts) in JS_TO_TS_EXTENSIONS {
+            if let Some(base) = path.strip_suffix(js_ext) {
+                return self
+                    .try_extensions(base, ts_exts, all_files)
+                    .or_else(|| self.try_extensions(base, &[js_ext], all_files));
+            }
+        }
+        if self.exists(path, all_files) {
+            return Some(path.to_string());
+        }
+        self.try_extensions(path, &SOURCE_EXTENSIONS, all_files)
+    }
+
+    // 目录中 package.json 的 exports、types、typings、main 指向的路径，按优先级排列
+    pub fn package_entries(&self, dir: &str) -> Vec<String> {
+        let Some(package_json) = self.packages.get(dir) else {
+            return vec![];
+        };
+        let exports_entry = self
+            .module_resolution
+            .conditions()
+            .zip(package_json.exports.as_ref())
+            .and_then(|(conditions, exports)| resolve_exports_entry(exports, conditions));
+        [
+            exports_entry.as_ref(),
+            package_json.types.as_ref(),
+            package_json.typings.as_ref(),
+            package_json.main.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|target| join_relative(dir, target))
+        .collect()
+    }
+
+    // 目录：package.json 的 exports、types、main，最后是 index 文件
+    fn resolve_directory(&self, dir: &str, all_files: &HashSet<String>) -> Option<String> {
+        for target in self.package_entries(dir) {
+            if let Some(file) = self.resolve_file(&target, all_files).or_else(|| {
+                self.try_extensions(&format!("{}/index", target), &SOURCE_EXTENSIONS, all_files)
+            }) {
+                return Some(file);
+            }
+        }
+        self.try_extensions(&format!("{}/index", dir), &SOURCE_EXTENSIONS, all_files)
+    }
+
+    // 无法对应到扫描范围内的文件时，带扩展名的路径（如图片）原样返回
+    pub fn resolve(&self, path: &str, all_files: &HashSet<String>) -> Option<String> {
+        self.resolve_file(path, all_files)
+            .or_else(|| self.resolve_directory(path, all_files))
+            .or_else(|| has_file_extension(path).then(|| path.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(files: &[&str]) -> HashSet<String> {
+        files.iter().map(|file| file.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_extensions() {
+        let all_files = files(&[
+            "src/utils.ts",
+            "src/utils/index.tsx",
+            "src/esm.mts",
+            "src/legacy.cjs",
+            "src/plain.js",
+            "src/widgets/index.js",
+            "src/types.d.ts",
+        ]);
+        let resolver = Resolver::default();
+        for (path, expected) in [
+            ("src/utils", "src/utils.ts"),
+            ("src/utils.js", "src/utils.ts"),
+            ("src/esm.mjs", "src/esm.mts"),
+            ("src/legacy", "src/legacy.cjs"),
+            ("src/plain.js", "src/plain.js"),
+            ("src/widgets", "src/widgets/index.js"),
+            ("src/types", "src/types.d.ts"),
+            ("src/logo.png", "src/logo.png"),
+        ] {
+            assert_eq!(
+                resolver.resolve(path, &all_files),
+                Some(expected.to_string()),
+                "{}",
+                path
+            );
+        }
+        assert_eq!(resolver.resolve("src/missing", &all_files), None);
+
+        let resolver = Resolver {
+            ignored_exts: HashSet::from(["ts".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolver.resolve("src/utils", &all_files),
+            Some("src/utils/index.tsx".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_subpath_import() {
+        let resolver = Resolver {
+            package_imports: Some(serde_json::json!({
+                "#internal/*": "./src/internal/*.js",
+                "#internal/config": { "node": "./src/config.node.ts", "default": "./src/config.ts" },
+                "#dep": "left-pad"
+            })),
+            packages: HashMap::from([
+                (
+                    "packages/ui".to_string(),
+                    PackageJson {
+                        imports: Some(serde_json::json!({ "#utils": "./src/utils.ts" })),
+                        ..Default::default()
+                    },
+                ),
+                ("packages/cli".to_string(), PackageJson::default()),
+            ]),
+            ..Default::default()
+        };
+        let resolve = |from: &str, specifier: &str| {
+            resolver
+                .resolve_subpath_import(from, specifier)
+                .map(|(scope, target)| format!("{}:{}", scope, target))
+        };
+        assert_eq!(
+            resolve("src/index.ts", "#internal/utils"),
+            Some(":./src/internal/utils.js".to_string())
+        );
+        assert_eq!(
+            resolve("src/index.ts", "#internal/config"),
+            Some(":./src/config.ts".to_string())
+        );
+        assert_eq!(
+            resolve("src/index.ts", "#dep"),
+            Some(":left-pad".to_string())
+        );
+        assert_eq!(resolve("src/index.ts", "#missing"), None);
+        // 工作区包使用自己 package.json 中的 imports，不会向上查找根目录
+        assert_eq!(
+            resolve("packages/ui/src/index.ts", "#utils"),
+            Some("packages/ui:./src/utils.ts".to_string())
+        );
+        assert_eq!(resolve("src/index.ts", "#utils"), None);
+        assert_eq!(resolve("packages/cli/src/index.ts", "#dep"), None);
+    }
+
+    #[test]
+    fn test_resolve_module_suffixes() {
+        let all_files = files(&["src/button.ios.ts", "src/button.ts", "src/list.ts"]);
+        let resolver = Resolver {
+            module_suffixes: vec![".ios".to_string(), String::new()],
+            ..Default::default()
+        };
+        assert_eq!(
+            resolver.resolve("src/button", &all_files),
+            Some("src/button.ios.ts".to_string())
+        );
+        assert_eq!(
+            resolver.resolve("src/list.js", &all_files),
+            Some("src/list.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_package_directory() {
+        let all_files = files(&[
+            "src/lib/src/entry.ts",
+            "src/lib/main.ts",
+            "src/lib/index.ts",
+        ]);
+        let package_json = || PackageJson {
+            main: Some("./main.js".to_string()),
+            exports: Some(serde_json::json!({
+                ".": { "import": "./src/entry.js", "require": "./main.js" },
+                "./package.json": "./package.json"
+            })),
+            ..Default::default()
+        };
+        let resolver = Resolver {
+            packages: HashMap::from([("src/lib".to_string(), package_json())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolver.resolve("src/lib", &all_files),
+            Some("src/lib/src/entry.ts".to_string())
+        );
+
+        // node10 不读取 exports
+        let resolver = Resolver {
+            module_resolution: ModuleResolution::Node10,
+            packages: HashMap::from([("src/lib".to_string(), package_json())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolver.resolve("src/lib", &all_files),
+            Some("src/lib/main.ts".to_string())
+        );
+    }
+}
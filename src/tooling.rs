@@ -0,0 +1,514 @@
+use crate::common::glob_in_project;
+use crate::dependencies::get_package_name;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// 项目根目录下的工具配置文件，如 vite.config.ts、.eslintrc.json、babel.config.js、postcss.config.js
+pub const CONFIG_FILE_PATTERNS: [&str; 14] = [
+    "*.config.js",
+    "*.config.cjs",
+    "*.config.mjs",
+    "*.config.ts",
+    "*.config.cts",
+    "*.config.mts",
+    "*.config.json",
+    ".*rc",
+    ".*rc.js",
+    ".*rc.cjs",
+    ".*rc.mjs",
+    ".*rc.json",
+    ".*rc.yml",
+    ".*rc.yaml",
+];
+
+// eslint、babel 等工具允许省略的包名前缀，如 plugins: ['react'] 指向 eslint-plugin-react
+const SHORTHAND_PREFIXES: [&str; 7] = [
+    "eslint-plugin-",
+    "eslint-config-",
+    "babel-plugin-",
+    "babel-preset-",
+    "prettier-plugin-",
+    "stylelint-config-",
+    "stylelint-",
+];
+
+// 这些命令会执行后面的另一个命令
+const COMMAND_WRAPPERS: [&str; 4] = ["cross-env", "dotenv", "env", "nohup"];
+
+// 执行 node_modules/.bin 中命令的方式：npx vite、pnpm exec vite、yarn dlx vite
+const PACKAGE_RUNNERS: [&str; 3] = ["npx", "pnpx", "bunx"];
+const PACKAGE_MANAGERS: [&str; 4] = ["npm", "pnpm", "yarn", "bun"];
+const EXEC_COMMANDS: [&str; 3] = ["exec", "dlx", "x"];
+
+// 预加载模块的参数，如 node -r ts-node/register、node --import tsx
+const REQUIRE_FLAGS: [&str; 4] = ["-r", "--require", "--import", "--loader"];
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageScripts {
+    scripts: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageBin {
+    name: Option<String>,
+    bin: Option<Value>,
+}
+
+fn is_env_assignment(token: &str) -> bool {
+    !token.starts_with('-')
+        && token
+            .split_once('=')
+            .is_some_and(|(key, _)| !key.is_empty() && !key.contains(['/', '.']))
+}
+
+// 返回脚本中执行的命令以及通过 -r/--import 预加载的模块
+pub fn get_script_commands(script: &str) -> Vec<String> {
+    let separator = Regex::new(r"&&|\|\||[;|&\n]").unwrap();
+    let mut commands = vec![];
+    for segment in separator.split(script) {
+        let tokens: Vec<&str> = segment
+            .split_whitespace()
+            .map(|token| token.trim_matches(['\'', '"']))
+            .filter(|token| !token.is_empty())
+            .collect();
+        for (index, token) in tokens.iter().enumerate() {
+            if REQUIRE_FLAGS.contains(token)
+                && let Some(module) = tokens.get(index + 1)
+            {
+                commands.push(module.to_string());
+            }
+        }
+
+        let mut tokens = tokens
+            .into_iter()
+            .filter(|token| !is_env_assignment(token))
+            .peekable();
+        while let Some(token) = tokens.next() {
+            if COMMAND_WRAPPERS.contains(&token) {
+                commands.push(token.to_string());
+                // 跳过包装命令自身的参数
+                while tokens.next_if(|token| token.starts_with('-')).is_some() {}
+                continue;
+            }
+            let command = if PACKAGE_RUNNERS.contains(&token) {
+                tokens.find(|token| !token.starts_with('-'))
+            } else if PACKAGE_MANAGERS.contains(&token) {
+                match tokens.next() {
+                    Some(sub_command) if EXEC_COMMANDS.contains(&sub_command) => {
+                        tokens.find(|token| !token.starts_with('-'))
+                    }
+                    // npm run build 等执行的是其他脚本或包管理器自身的命令
+                    _ => None,
+                }
+            } else {
+                Some(token)
+            };
+            commands.extend(command.map(String::from));
+            break;
+        }
+    }
+    commands
+}
+
+// 命令名到包名的映射，来自已声明依赖在 node_modules 中 package.json 的 bin 字段
+pub fn get_bin_packages(
+    project_root: &str,
+    declared_dependencies: &HashSet<String>,
+) -> HashMap<String, String> {
+    let mut bin_packages = HashMap::new();
+    for dependency in declared_dependencies {
+        let package_json_path = Path::new(project_root)
+            .join("node_modules")
+            .join(dependency)
+            .join("package.json");
+        let Some(package_bin) = fs::read_to_string(package_json_path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<PackageBin>(&json).ok())
+        else {
+            continue;
+        };
+        match package_bin.bin {
+            // "bin": "cli.js" 时命令名为不带 scope 的包名
+            Some(Value::String(_)) => {
+                let name = package_bin.name.as_deref().unwrap_or(dependency);
+                let bin = name.rsplit('/').next().unwrap_or(name);
+                bin_packages.insert(bin.to_string(), dependency.to_string());
+            }
+            Some(Value::Object(bins)) => {
+                for bin in bins.keys() {
+                    bin_packages.insert(bin.to_string(), dependency.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    bin_packages
+}
+
+// package.json 的 scripts 中用到的依赖，以及用到它们的脚本名
+pub fn get_script_dependencies(
+    project_root: &str,
+    declared_dependencies: &HashSet<String>,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let scripts = fs::read_to_string(Path::new(project_root).join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<PackageScripts>(&json).ok())
+        .and_then(|package_scripts| package_scripts.scripts)
+        .unwrap_or_default();
+    let bin_packages = get_bin_packages(project_root, declared_dependencies);
+    let mut script_dependencies: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (name, script) in &scripts {
+        for command in get_script_commands(script) {
+            // 没有安装依赖时，假设命令名与包名相同
+            let package = bin_packages
+                .get(&command)
+                .map(String::as_str)
+                .unwrap_or_else(|| get_package_name(&command));
+            if declared_dependencies.contains(package) {
+                script_dependencies
+                    .entry(package.to_string())
+                    .or_default()
+                    .insert(format!("scripts.{}", name));
+            }
+        }
+    }
+    script_dependencies
+}
+
+// 值为插件或预设名的配置项，这些名字可以省略包名前缀
+const PLUGIN_KEYS: [&str; 3] = ["plugins", "presets", "extends"];
+
+#[derive(Debug, PartialEq)]
+enum ConfigToken {
+    // 字符串字面量的内容，包括 import/require 的来源
+    Str(String),
+    // 标识符、对象的键以及 YAML 中不带引号的值
+    Word(String),
+    Punct(char),
+}
+
+// 将配置文件拆分为字符串、单词与标点，跳过 //、/* */ 与 # 注释
+fn tokenize_config(code: &str) -> Vec<ConfigToken> {
+    let chars: Vec<char> = code.chars().collect();
+    let is_word_char = |c: char| c.is_alphanumeric() || "_$@/.-".contains(c);
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' | '`' => {
+                let start = i + 1;
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                let value: String = chars[start..i.min(chars.len())].iter().collect();
+                // 带有表达式的模板字符串不是静态的包名
+                if !(c == '`' && value.contains("${")) {
+                    tokens.push(ConfigToken::Str(value));
+                }
+                i += 1;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            // YAML 注释
+            '#' if i == 0 || chars[i - 1].is_whitespace() => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            c if c.is_whitespace() => i += 1,
+            c if c.is_alphanumeric() || "_$@".contains(c) => {
+                let start = i;
+                while i < chars.len() && is_word_char(chars[i]) {
+                    i += 1;
+                }
+                tokens.push(ConfigToken::Word(chars[start..i].iter().collect()));
+            }
+            c => {
+                tokens.push(ConfigToken::Punct(c));
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+fn token_text(token: Option<&ConfigToken>) -> Option<&str> {
+    match token? {
+        ConfigToken::Str(text) | ConfigToken::Word(text) => Some(text),
+        ConfigToken::Punct(_) => None,
+    }
+}
+
+// plugins/presets/extends 的值中的名字：字符串、数组（包括嵌套的 [name, options]）、
+// 以名字为键的对象，以及 YAML 的 - name 列表
+fn get_plugin_values(tokens: &[ConfigToken], start: usize) -> Vec<&str> {
+    let mut values = vec![];
+    match tokens.get(start) {
+        Some(ConfigToken::Str(value)) => values.push(value.as_str()),
+        Some(ConfigToken::Punct(open @ ('[' | '{'))) => {
+            let mut depth = 0;
+            for (index, token) in tokens.iter().enumerate().skip(start) {
+                match token {
+                    ConfigToken::Punct('[' | '{' | '(') => depth += 1,
+                    ConfigToken::Punct(']' | '}' | ')') => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    ConfigToken::Str(value) => values.push(value.as_str()),
+                    ConfigToken::Word(key)
+                        if *open == '{'
+                            && depth == 1
+                            && tokens.get(index + 1) == Some(&ConfigToken::Punct(':')) =>
+                    {
+                        values.push(key.as_str())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Some(ConfigToken::Punct('-')) => {
+            let mut index = start;
+            while tokens.get(index) == Some(&ConfigToken::Punct('-')) {
+                let Some(value) = token_text(tokens.get(index + 1)) else {
+                    break;
+                };
+                values.push(value);
+                index += 2;
+            }
+        }
+        _ => {}
+    }
+    values
+}
+
+// 插件名可能省略的前缀：react -> eslint-plugin-react，@scope -> @scope/eslint-plugin
+fn get_shorthand_candidates(name: &str) -> Vec<String> {
+    match name.split_once('/') {
+        // @scope/name -> @scope/eslint-plugin-name
+        Some((scope, name)) => SHORTHAND_PREFIXES
+            .iter()
+            .map(|prefix| format!("{}/{}{}", scope, prefix, name))
+            .collect(),
+        // @scope -> @scope/eslint-plugin
+        None if name.starts_with('@') => SHORTHAND_PREFIXES
+            .iter()
+            .map(|prefix| format!("{}/{}", name, prefix.trim_end_matches('-')))
+            .collect(),
+        None => SHORTHAND_PREFIXES
+            .iter()
+            .map(|prefix| format!("{}{}", prefix, name))
+            .collect(),
+    }
+}
+
+// 配置文件中引用的已声明依赖：字符串字面量（包括 import/require 的来源）中的包名，
+// 以及 plugins、presets、extends 中的插件与预设名和它们的简写。注释、变量名与其他对象的键不算引用
+pub fn get_config_references(
+    code: &str,
+    declared_dependencies: &HashSet<String>,
+) -> HashSet<String> {
+    let tokens = tokenize_config(code);
+    let mut candidates: Vec<String> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            ConfigToken::Str(value) => Some(get_package_name(value).to_string()),
+            _ => None,
+        })
+        .collect();
+    for (index, token) in tokens.iter().enumerate() {
+        if !token_text(Some(token)).is_some_and(|key| PLUGIN_KEYS.contains(&key))
+            || tokens.get(index + 1) != Some(&ConfigToken::Punct(':'))
+        {
+            continue;
+        }
+        for value in get_plugin_values(&tokens, index + 2) {
+            // eslint 的 plugin:react/recommended 指向插件 react 中的配置
+            let name = match value.strip_prefix("plugin:") {
+                Some(plugin) => plugin.rsplit_once('/').map_or(plugin, |(name, _)| name),
+                None => get_package_name(value),
+            };
+            candidates.push(name.to_string());
+            candidates.extend(get_shorthand_candidates(name));
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|candidate| declared_dependencies.contains(candidate))
+        .collect()
+}
+
+// 项目根目录下每个工具配置文件引用的依赖
+pub fn get_config_file_dependencies(
+    project_root: &str,
+    declared_dependencies: &HashSet<String>,
+) -> HashMap<String, HashSet<String>> {
+    let config_files: BTreeSet<String> = CONFIG_FILE_PATTERNS
+        .iter()
+        .flat_map(|pattern| glob_in_project(project_root, pattern))
+        .collect();
+    config_files
+        .into_iter()
+        .filter_map(|file| {
+            let code = fs::read_to_string(Path::new(project_root).join(&file)).ok()?;
+            let references = get_config_references(&code, declared_dependencies);
+            (!references.is_empty()).then_some((file, references))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_script_commands() {
+        for (script, commands) in [
+            ("vite build", vec!["vite"]),
+            ("tsc -b && vite build", vec!["tsc", "vite"]),
+            (
+                "NODE_ENV=production cross-env FOO=1 webpack --mode production",
+                vec!["cross-env", "webpack"],
+            ),
+            ("npx eslint . --fix", vec!["eslint"]),
+            ("pnpm exec vitest run", vec!["vitest"]),
+            ("npm run build && husky", vec!["husky"]),
+            (
+                "node -r ts-node/register ./scripts/seed.ts",
+                vec!["ts-node/register", "node"],
+            ),
+            ("node --import tsx src/index.ts", vec!["tsx", "node"]),
+            (
+                "prettier --write . ; stylelint '**/*.css'",
+                vec!["prettier", "stylelint"],
+            ),
+        ] {
+            assert_eq!(get_script_commands(script), commands, "{}", script);
+        }
+    }
+
+    #[test]
+    fn test_get_config_references() {
+        let declared = HashSet::from(
+            [
+                "vite",
+                "@vitejs/plugin-react",
+                "eslint-plugin-react",
+                "eslint-config-prettier",
+                "@typescript-eslint/eslint-plugin",
+                "@babel/preset-env",
+                "tailwindcss",
+                "autoprefixer",
+                "lodash",
+            ]
+            .map(String::from),
+        );
+        let code = r#"
+import { defineConfig } from 'vite'
+import react from '@vitejs/plugin-react'
+module.exports = {
+  extends: ['plugin:react/recommended', 'prettier'],
+  plugins: ['@typescript-eslint'],
+  presets: ['@babel/preset-env'],
+}
+export default { plugins: { tailwindcss: {}, autoprefixer: {} } }
+        "#;
+        assert_eq!(
+            get_config_references(code, &declared),
+            HashSet::from(
+                [
+                    "vite",
+                    "@vitejs/plugin-react",
+                    "eslint-plugin-react",
+                    "eslint-config-prettier",
+                    "@typescript-eslint/eslint-plugin",
+                    "@babel/preset-env",
+                    "tailwindcss",
+                    "autoprefixer",
+                ]
+                .map(String::from)
+            )
+        );
+    }
+
+    #[test]
+    fn test_get_config_references_ignores_non_references() {
+        let declared = HashSet::from(
+            [
+                "lodash",
+                "eslint-plugin-react",
+                "prettier",
+                "stylelint-config-standard",
+            ]
+            .map(String::from),
+        );
+        let code = r#"
+// lodash is no longer needed here
+/* prettier runs separately */
+const react = true;
+export default { lodash: false, build: { react } }
+        "#;
+        assert!(get_config_references(code, &declared).is_empty());
+
+        let yaml = r#"
+# react 插件
+extends:
+  - standard
+plugins:
+  - react
+        "#;
+        assert_eq!(
+            get_config_references(yaml, &declared),
+            HashSet::from(["stylelint-config-standard", "eslint-plugin-react"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn test_get_script_dependencies() {
+        let dir = std::env::temp_dir().join(format!("minipp-scripts-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("node_modules/typescript")).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "scripts": { "build": "tsc -b && vite build", "lint": "eslint .", "dev": "vite" } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("node_modules/typescript/package.json"),
+            r#"{ "name": "typescript", "bin": { "tsc": "./bin/tsc", "tsserver": "./bin/tsserver" } }"#,
+        )
+        .unwrap();
+        let declared = HashSet::from(["typescript", "vite", "react"].map(String::from));
+        assert_eq!(
+            get_script_dependencies(dir.to_str().unwrap(), &declared),
+            BTreeMap::from([
+                (
+                    "typescript".to_string(),
+                    BTreeSet::from(["scripts.build".to_string()])
+                ),
+                (
+                    "vite".to_string(),
+                    BTreeSet::from(["scripts.build", "scripts.dev"].map(String::from))
+                ),
+            ])
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,382 @@
+use crate::common::strip_json_comments;
+use path_clean::clean;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::cmp::Reverse;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const TS_CONFIG_FILES: [&str; 2] = ["tsconfig.json", "jsconfig.json"];
+
+// extends 链的最大深度，避免循环引用
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+// 按声明顺序保存 paths，前缀长度相同的规则由先声明的胜出
+#[derive(Default, Debug)]
+struct OrderedPaths(Vec<(String, Vec<String>)>);
+
+impl<'de> Deserialize<'de> for OrderedPaths {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PathsVisitor;
+
+        impl<'de> Visitor<'de> for PathsVisitor {
+            type Value = OrderedPaths;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of path patterns")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut paths = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    paths.push(entry);
+                }
+                Ok(OrderedPaths(paths))
+            }
+        }
+
+        deserializer.deserialize_map(PathsVisitor)
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct RawCompilerOptions {
+    #[serde(rename = "baseUrl")]
+    base_url: Option<String>,
+    paths: Option<OrderedPaths>,
+    #[serde(rename = "moduleResolution")]
+    module_resolution: Option<String>,
+    #[serde(rename = "moduleSuffixes")]
+    module_suffixes: Option<Vec<String>>,
+    types: Option<Vec<String>>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct RawTsConfig {
+    extends: Option<Extends>,
+    #[serde(rename = "compilerOptions")]
+    compiler_options: Option<RawCompilerOptions>,
+}
+
+// 所有路径均为相对于项目根目录、以 / 分隔的路径
+#[derive(Default, Debug, PartialEq)]
+pub struct TsConfig {
+    pub base_url: Option<String>,
+    pub paths: Vec<(String, Vec<String>)>,
+    // paths 中目标路径的基准目录：有 baseUrl 时为 baseUrl，否则为声明 paths 的配置文件所在目录
+    pub paths_base: String,
+    pub module_resolution: Option<String>,
+    pub module_suffixes: Vec<String>,
+    // compilerOptions.types 中列出的类型包，如 node、vitest/globals
+    pub types: Vec<String>,
+}
+
+fn to_relative_string(path: PathBuf) -> String {
+    let path = clean(path).to_string_lossy().replace('\\', "/");
+    if path == "." { String::new() } else { path }
+}
+
+fn join_relative(base: &str, path: &str) -> String {
+    to_relative_string(Path::new(base).join(path))
+}
+
+impl TsConfig {
+    pub fn load(project_root: &str) -> Self {
+        Self::load_package(project_root, "").unwrap_or_default()
+    }
+
+    // 工作区包目录中的 tsconfig，没有时返回 None；路径仍相对于项目根目录
+    pub fn load_package(project_root: &str, package_dir: &str) -> Option<Self> {
+        TS_CONFIG_FILES
+            .iter()
+            .map(|file| join_relative(package_dir, file))
+            .find(|file| Path::new(project_root).join(file).is_file())
+            .and_then(|file| Self::load_file(project_root, &file, 0))
+    }
+
+    fn load_file(project_root: &str, config_path: &str, depth: usize) -> Option<Self> {
+        if depth > MAX_EXTENDS_DEPTH {
+            println!("警告: {} 的 extends 层级过深", config_path);
+            return None;
+        }
+        let json = fs::read_to_string(Path::new(project_root).join(config_path)).ok()?;
+        let raw: RawTsConfig = match serde_json::from_str(&strip_json_comments(&json)) {
+            Ok(raw) => raw,
+            Err(e) => {
+                println!("警告: 无法解析 {}: {}", config_path, e);
+                return None;
+            }
+        };
+        let config_dir = to_relative_string(
+            Path::new(config_path)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .to_path_buf(),
+        );
+
+        let extends = match raw.extends {
+            Some(Extends::Single(extend)) => vec![extend],
+            Some(Extends::Multiple(extends)) => extends,
+            None => vec![],
+        };
+        // 后面的 extends 覆盖前面的，当前文件覆盖所有 extends
+        let mut config = Self::default();
+        for extend in extends {
+            let Some(extend_path) = resolve_extends(project_root, &config_dir, &extend) else {
+                println!("警告: 找不到 {} 中 extends 的 {}", config_path, extend);
+                continue;
+            };
+            if let Some(parent) = Self::load_file(project_root, &extend_path, depth + 1) {
+                config.merge(parent);
+            }
+        }
+
+        let options = raw.compiler_options.unwrap_or_default();
+        let own = Self {
+            base_url: options
+                .base_url
+                .map(|base_url| join_relative(&config_dir, &base_url)),
+            paths: options.paths.unwrap_or_default().0,
+            paths_base: config_dir,
+            module_resolution: options.module_resolution,
+            module_suffixes: options.module_suffixes.unwrap_or_default(),
+            types: options.types.unwrap_or_default(),
+        };
+        config.merge(own);
+        Some(config)
+    }
+
+    fn merge(&mut self, other: Self) {
+        if other.base_url.is_some() {
+            self.base_url = other.base_url;
+        }
+        if !other.paths.is_empty() {
+            self.paths = other.paths;
+            self.paths_base = other.paths_base;
+        }
+        if other.module_resolution.is_some() {
+            self.module_resolution = other.module_resolution;
+        }
+        if !other.module_suffixes.is_empty() {
+            self.module_suffixes = other.module_suffixes;
+        }
+        if !other.types.is_empty() {
+            self.types = other.types;
+        }
+    }
+
+    // 按 tsc 的规则匹配 paths：精确匹配优先，其次取前缀最长的通配符匹配，长度相同时取先声明的
+    fn match_paths<'a>(&'a self, specifier: &'a str) -> Option<(&'a [String], &'a str)> {
+        if let Some((_, targets)) = self.paths.iter().find(|(pattern, _)| pattern == specifier) {
+            return Some((targets, ""));
+        }
+        self.paths
+            .iter()
+            .filter_map(|(pattern, targets)| {
+                let (prefix, suffix) = pattern.split_once('*')?;
+                let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((prefix.len(), targets.as_slice(), matched))
+            })
+            .min_by_key(|(prefix_len, _, _)| Reverse(*prefix_len))
+            .map(|(_, targets, matched)| (targets, matched))
+    }
+
+    // 返回非相对导入在 paths 与 baseUrl 下的候选路径，按优先级排列
+    pub fn resolve_candidates(&self, specifier: &str) -> Vec<String> {
+        if specifier.starts_with('.') || specifier.starts_with('/') {
+            return vec![];
+        }
+        let paths_base = self.base_url.as_deref().unwrap_or(&self.paths_base);
+        let mut candidates: Vec<String> = self
+            .match_paths(specifier)
+            .map(|(targets, matched)| {
+                targets
+                    .iter()
+                    .map(|target| join_relative(paths_base, &target.replacen('*', matched, 1)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(base_url) = &self.base_url {
+            candidates.push(join_relative(base_url, specifier));
+        }
+        candidates
+    }
+}
+
+// extends 支持相对路径与 node_modules 中的包
+fn resolve_extends(project_root: &str, config_dir: &str, extend: &str) -> Option<String> {
+    let base = if extend.starts_with('.') || extend.starts_with('/') {
+        join_relative(config_dir, extend)
+    } else {
+        join_relative("node_modules", extend)
+    };
+    let root = Path::new(project_root);
+    [
+        base.clone(),
+        format!("{}.json", base),
+        format!("{}/tsconfig.json", base),
+    ]
+    .into_iter()
+    .find(|candidate| root.join(candidate).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("minipp-ts-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_candidates() {
+        let config = TsConfig {
+            base_url: Some("src".to_string()),
+            paths: vec![
+                (
+                    "~components/*".to_string(),
+                    vec!["components/*".to_string()],
+                ),
+                (
+                    "@app/*".to_string(),
+                    vec!["app/*".to_string(), "shared/*".to_string()],
+                ),
+                (
+                    "@app/config".to_string(),
+                    vec!["config/index.ts".to_string()],
+                ),
+                ("#utils".to_string(), vec!["utils/index".to_string()]),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.resolve_candidates("~components/Button"),
+            vec!["src/components/Button", "src/~components/Button"]
+        );
+        assert_eq!(
+            config.resolve_candidates("@app/store"),
+            vec!["src/app/store", "src/shared/store", "src/@app/store"]
+        );
+        assert_eq!(
+            config.resolve_candidates("@app/config"),
+            vec!["src/config/index.ts", "src/@app/config"]
+        );
+        assert_eq!(
+            config.resolve_candidates("#utils"),
+            vec!["src/utils/index", "src/#utils"]
+        );
+        assert!(config.resolve_candidates("./local").is_empty());
+    }
+
+    #[test]
+    fn test_load_with_extends() {
+        let dir = temp_project("extends");
+        fs::create_dir_all(dir.join("configs")).unwrap();
+        fs::write(
+            dir.join("configs/tsconfig.base.json"),
+            r#"{
+                // 基础配置
+                "compilerOptions": {
+                    "baseUrl": "..",
+                    "paths": { "@app/*": ["src/app/*"] },
+                    "moduleResolution": "Bundler",
+                    "types": ["node", "vitest/globals"],
+                },
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "extends": "./configs/tsconfig.base.json", "compilerOptions": { "moduleSuffixes": [".ios", ""] } }"#,
+        )
+        .unwrap();
+
+        let config = TsConfig::load(dir.to_str().unwrap());
+        assert_eq!(config.base_url, Some(String::new()));
+        assert_eq!(config.module_resolution, Some("Bundler".to_string()));
+        assert_eq!(config.module_suffixes, vec![".ios", ""]);
+        assert_eq!(config.types, vec!["node", "vitest/globals"]);
+        assert_eq!(
+            config.resolve_candidates("@app/store"),
+            vec!["src/app/store", "@app/store"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_package() {
+        let dir = temp_project("package");
+        fs::create_dir_all(dir.join("packages/ui")).unwrap();
+        fs::write(
+            dir.join("packages/ui/tsconfig.json"),
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "~/*": ["./src/*"] } } }"#,
+        )
+        .unwrap();
+
+        let project_root = dir.to_str().unwrap();
+        let config = TsConfig::load_package(project_root, "packages/ui").unwrap();
+        assert_eq!(
+            config.resolve_candidates("~/button"),
+            vec!["packages/ui/src/button", "packages/ui/~/button"]
+        );
+        assert_eq!(TsConfig::load_package(project_root, "packages/utils"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_paths_keep_declaration_order() {
+        let dir = temp_project("paths-order");
+        let project_root = dir.to_str().unwrap();
+        for (paths, expected) in [
+            (
+                r#"{ "lib/*": ["first/*"], "lib/*.ts": ["second/*"] }"#,
+                "first/a.ts",
+            ),
+            (
+                r#"{ "lib/*.ts": ["second/*"], "lib/*": ["first/*"] }"#,
+                "second/a",
+            ),
+        ] {
+            fs::write(
+                dir.join("tsconfig.json"),
+                format!(r#"{{ "compilerOptions": {{ "paths": {} }} }}"#, paths),
+            )
+            .unwrap();
+            let config = TsConfig::load(project_root);
+            assert_eq!(config.resolve_candidates("lib/a.ts"), vec![expected]);
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_paths_without_base_url() {
+        let dir = temp_project("no-base-url");
+        fs::create_dir_all(dir.join("configs")).unwrap();
+        fs::write(
+            dir.join("configs/tsconfig.base.json"),
+            r#"{ "compilerOptions": { "paths": { "~/*": ["../src/*"] } } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "extends": ["./configs/tsconfig.base"] }"#,
+        )
+        .unwrap();
+
+        let config = TsConfig::load(dir.to_str().unwrap());
+        assert_eq!(config.resolve_candidates("~/utils"), vec!["src/utils"]);
+        assert!(config.resolve_candidates("react").is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,460 @@
+use crate::common::{glob_in_project, read_project_dependencies};
+use crate::dependencies::get_package_name;
+use crate::resolver::Resolver;
+use glob::Pattern;
+use path_clean::clean;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+pub const PNPM_WORKSPACE_FILE: &str = "pnpm-workspace.yaml";
+
+// 包的入口指向这些构建产物目录时，改为查找 src 下的同名源码
+pub const BUILD_DIRS: [&str; 6] = ["dist", "lib", "build", "esm", "cjs", "out"];
+
+// 每个工作区包默认扫描的源码目录
+pub const WORKSPACE_SOURCE_DIR: &str = "src";
+
+// dir 为相对于项目根目录、以 / 分隔的路径
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workspace {
+    pub name: String,
+    pub dir: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageWorkspaces {
+    workspaces: Option<Value>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageName {
+    name: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+struct PackageEntryFields {
+    main: Option<String>,
+    module: Option<String>,
+    types: Option<String>,
+    typings: Option<String>,
+    exports: Option<Value>,
+    bin: Option<Value>,
+}
+
+fn join_relative(base: &str, path: &str) -> String {
+    let path = clean(Path::new(base).join(path))
+        .to_string_lossy()
+        .replace('\\', "/");
+    if path == "." { String::new() } else { path }
+}
+
+// pnpm-workspace.yaml 中的 packages 列表，只处理块列表与 [a, b] 两种写法
+fn parse_pnpm_workspace(yaml: &str) -> Vec<String> {
+    let unquote = |value: &str| value.trim().trim_matches(['\'', '"']).trim().to_string();
+    let mut patterns = vec![];
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let content = line.split(" #").next().unwrap_or_default();
+        if content.trim().is_empty() || content.trim_start().starts_with('#') {
+            continue;
+        }
+        if let Some(value) = content.strip_prefix("packages:") {
+            in_packages = true;
+            if let Some(list) = value.trim().strip_prefix('[') {
+                patterns.extend(
+                    list.trim_end_matches(']')
+                        .split(',')
+                        .map(unquote)
+                        .filter(|pattern| !pattern.is_empty()),
+                );
+                in_packages = false;
+            }
+            continue;
+        }
+        if !content.starts_with([' ', '\t', '-']) {
+            in_packages = false;
+        }
+        if in_packages && let Some(value) = content.trim_start().strip_prefix('-') {
+            patterns.push(unquote(value));
+        }
+    }
+    patterns
+}
+
+// 工作区规则：优先读取 pnpm-workspace.yaml，其次是 package.json 的 workspaces（数组或 { packages: [] }）
+pub fn get_workspace_patterns(project_root: &str) -> Vec<String> {
+    if let Ok(yaml) = fs::read_to_string(Path::new(project_root).join(PNPM_WORKSPACE_FILE)) {
+        return parse_pnpm_workspace(&yaml);
+    }
+    let workspaces = fs::read_to_string(Path::new(project_root).join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<PackageWorkspaces>(&json).ok())
+        .and_then(|package_workspaces| package_workspaces.workspaces);
+    let patterns = match workspaces {
+        Some(Value::Array(patterns)) => patterns,
+        Some(Value::Object(mut map)) => match map.remove("packages") {
+            Some(Value::Array(patterns)) => patterns,
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+    patterns
+        .into_iter()
+        .filter_map(|pattern| pattern.as_str().map(String::from))
+        .collect()
+}
+
+// 匹配工作区规则且带有 package.json 的目录，! 开头的规则用于排除
+pub fn find_workspaces(project_root: &str) -> Vec<Workspace> {
+    let patterns = get_workspace_patterns(project_root);
+    let (negated, patterns): (Vec<_>, Vec<_>) = patterns
+        .iter()
+        .map(|pattern| pattern.trim_start_matches("./").trim_end_matches('/'))
+        .partition(|pattern| pattern.starts_with('!'));
+    let negated: Vec<Pattern> = negated
+        .iter()
+        .filter_map(|pattern| Pattern::new(&pattern[1..]).ok())
+        .collect();
+    let mut workspaces = BTreeMap::new();
+    for pattern in patterns {
+        for package_json in glob_in_project(project_root, &format!("{}/package.json", pattern)) {
+            let dir = Path::new(&package_json)
+                .parent()
+                .and_then(|dir| dir.to_str())
+                .unwrap_or_default()
+                .to_string();
+            if dir.is_empty()
+                || dir.split('/').any(|part| part == "node_modules")
+                || negated.iter().any(|pattern| pattern.matches(&dir))
+            {
+                continue;
+            }
+            let name = fs::read_to_string(Path::new(project_root).join(&package_json))
+                .ok()
+                .and_then(|json| serde_json::from_str::<PackageName>(&json).ok())
+                .and_then(|package_name| package_name.name);
+            // 没有 name 的包无法通过包名导入
+            if let Some(name) = name {
+                workspaces.insert(dir.clone(), Workspace { name, dir });
+            }
+        }
+    }
+    workspaces.into_values().collect()
+}
+
+// 没有配置 include 时扫描根目录与每个工作区包的 src
+pub fn get_workspace_include(workspaces: &[Workspace]) -> Vec<String> {
+    std::iter::once(WORKSPACE_SOURCE_DIR.to_string())
+        .chain(
+            workspaces
+                .iter()
+                .map(|workspace| format!("{}/{}", workspace.dir, WORKSPACE_SOURCE_DIR)),
+        )
+        .collect()
+}
+
+// 文件所属的工作区包，嵌套时取最内层的包
+pub fn find_workspace<'a>(workspaces: &'a [Workspace], file: &str) -> Option<&'a Workspace> {
+    workspaces
+        .iter()
+        .filter(|workspace| {
+            file.strip_prefix(&workspace.dir)
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|workspace| workspace.dir.len())
+}
+
+// 包内的路径对应的源码文件，指向 dist 等构建产物时改为查找 src 下的同名文件
+pub fn resolve_package_source(
+    package_dir: &str,
+    path: &str,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> Option<String> {
+    if let Some(file) = resolver
+        .resolve(path, all_files)
+        .filter(|file| all_files.contains(file))
+    {
+        return Some(file);
+    }
+    let relative = if package_dir.is_empty() {
+        path
+    } else {
+        path.strip_prefix(package_dir)?.strip_prefix('/')?
+    };
+    let (build_dir, rest) = relative.split_once('/')?;
+    if !BUILD_DIRS.contains(&build_dir) {
+        return None;
+    }
+    // dist/index.d.ts 与 dist/index.js 都对应 src/index
+    let rest = rest.strip_suffix(".d.ts").unwrap_or(rest);
+    let rest = match Path::new(rest).extension() {
+        Some(ext) => &rest[..rest.len() - ext.len() - 1],
+        None => rest,
+    };
+    let source = join_relative(package_dir, &format!("{}/{}", WORKSPACE_SOURCE_DIR, rest));
+    resolver
+        .resolve(&source, all_files)
+        .filter(|file| all_files.contains(file))
+}
+
+// 工作区包名导入（如 @our/ui、@our/ui/button）对应的源码文件
+pub fn resolve_workspace_import(
+    workspace: &Workspace,
+    subpath: &str,
+    all_files: &HashSet<String>,
+    resolver: &Resolver,
+) -> Option<String> {
+    let path = join_relative(&workspace.dir, subpath);
+    if let Some(file) = resolve_package_source(&workspace.dir, &path, all_files, resolver) {
+        return Some(file);
+    }
+    if subpath.is_empty()
+        && let Some(file) = resolver
+            .package_entries(&workspace.dir)
+            .iter()
+            .find_map(|entry| resolve_package_source(&workspace.dir, entry, all_files, resolver))
+    {
+        return Some(file);
+    }
+    let source = join_relative(
+        &workspace.dir,
+        &format!("{}/{}", WORKSPACE_SOURCE_DIR, subpath),
+    );
+    resolver
+        .resolve(&source, all_files)
+        .filter(|file| all_files.contains(file))
+}
+
+// 声明了 main/module/types/exports 供其他包导入的库；应用与命令行工具（bin）不会被其他包依赖
+pub fn is_library_package(project_root: &str, workspace: &Workspace) -> bool {
+    fs::read_to_string(
+        Path::new(project_root)
+            .join(&workspace.dir)
+            .join("package.json"),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str::<PackageEntryFields>(&json).ok())
+    .is_some_and(|fields| {
+        fields.bin.is_none()
+            && (fields.main.is_some()
+                || fields.module.is_some()
+                || fields.types.is_some()
+                || fields.typings.is_some()
+                || fields.exports.is_some())
+    })
+}
+
+// 没有被根目录或其他工作区包在 package.json 中声明、也没有被它们通过包名导入的库，返回包名到目录的映射
+pub fn get_unused_workspaces(
+    project_root: &str,
+    workspaces: &[Workspace],
+    file_dependencies: &HashMap<String, HashSet<String>>,
+) -> BTreeMap<String, String> {
+    let declared: Vec<(String, HashSet<String>)> = std::iter::once(String::new())
+        .chain(workspaces.iter().map(|workspace| workspace.dir.clone()))
+        .map(|dir| {
+            let package_root = Path::new(project_root).join(&dir);
+            let dependencies =
+                read_project_dependencies(&package_root.to_string_lossy()).all_dependencies();
+            (dir, dependencies)
+        })
+        .collect();
+    workspaces
+        .iter()
+        .filter(|workspace| is_library_package(project_root, workspace))
+        .filter(|workspace| {
+            let declared_elsewhere = declared.iter().any(|(dir, dependencies)| {
+                dir != &workspace.dir && dependencies.contains(&workspace.name)
+            });
+            let imported_elsewhere = file_dependencies.iter().any(|(file, dependencies)| {
+                find_workspace(workspaces, file).is_none_or(|owner| owner.dir != workspace.dir)
+                    && dependencies
+                        .iter()
+                        .any(|dependency| get_package_name(dependency) == workspace.name)
+            });
+            !declared_elsewhere && !imported_elsewhere
+        })
+        .map(|workspace| (workspace.name.clone(), workspace.dir.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsconfig::TsConfig;
+
+    fn temp_project(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("minipp-ws-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_pnpm_workspace() {
+        let yaml = r#"
+# 工作区
+packages:
+  - 'packages/*'
+  - "apps/**" # 应用
+  - '!**/test/**'
+catalog:
+  react: ^19
+"#;
+        assert_eq!(
+            parse_pnpm_workspace(yaml),
+            vec!["packages/*", "apps/**", "!**/test/**"]
+        );
+        assert_eq!(
+            parse_pnpm_workspace("packages: ['packages/*', \"tools/*\"]"),
+            vec!["packages/*", "tools/*"]
+        );
+    }
+
+    #[test]
+    fn test_find_workspaces() {
+        let dir = temp_project("find");
+        for (path, json) in [
+            (
+                "package.json",
+                r#"{ "workspaces": { "packages": ["packages/*", "!packages/legacy"] } }"#,
+            ),
+            ("packages/ui/package.json", r#"{ "name": "@our/ui" }"#),
+            ("packages/utils/package.json", r#"{ "name": "@our/utils" }"#),
+            (
+                "packages/legacy/package.json",
+                r#"{ "name": "@our/legacy" }"#,
+            ),
+            (
+                "packages/ui/node_modules/x/package.json",
+                r#"{ "name": "x" }"#,
+            ),
+        ] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, json).unwrap();
+        }
+        let workspaces = find_workspaces(dir.to_str().unwrap());
+        assert_eq!(
+            workspaces,
+            vec![
+                Workspace {
+                    name: "@our/ui".to_string(),
+                    dir: "packages/ui".to_string()
+                },
+                Workspace {
+                    name: "@our/utils".to_string(),
+                    dir: "packages/utils".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            find_workspace(&workspaces, "packages/ui/src/index.ts").map(|w| w.name.as_str()),
+            Some("@our/ui")
+        );
+        assert_eq!(
+            find_workspace(&workspaces, "packages/ui-kit/src/index.ts"),
+            None
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_unused_workspaces() {
+        let dir = temp_project("unused");
+        for (path, json) in [
+            (
+                "package.json",
+                r#"{ "workspaces": ["packages/*", "apps/*"] }"#,
+            ),
+            (
+                "apps/web/package.json",
+                r#"{ "name": "@our/web", "dependencies": { "@our/ui": "workspace:*" } }"#,
+            ),
+            (
+                "packages/ui/package.json",
+                r#"{ "name": "@our/ui", "main": "dist/index.js" }"#,
+            ),
+            (
+                "packages/utils/package.json",
+                r#"{ "name": "@our/utils", "exports": "./src/index.ts" }"#,
+            ),
+            (
+                "packages/dead/package.json",
+                r#"{ "name": "@our/dead", "types": "src/index.ts" }"#,
+            ),
+            (
+                "packages/cli/package.json",
+                r#"{ "name": "@our/cli", "main": "index.js", "bin": "index.js" }"#,
+            ),
+        ] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, json).unwrap();
+        }
+        let project_root = dir.to_str().unwrap();
+        let workspaces = find_workspaces(project_root);
+        // @our/utils 没有被声明，但被 @our/ui 通过包名导入；@our/dead 只导入了自己
+        let file_dependencies = HashMap::from([
+            (
+                "packages/ui/src/index.ts".to_string(),
+                HashSet::from(["@our/utils/format".to_string()]),
+            ),
+            (
+                "packages/dead/src/index.ts".to_string(),
+                HashSet::from(["@our/dead".to_string()]),
+            ),
+        ]);
+        assert_eq!(
+            get_unused_workspaces(project_root, &workspaces, &file_dependencies),
+            BTreeMap::from([("@our/dead".to_string(), "packages/dead".to_string())])
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_workspace_import() {
+        let dir = temp_project("resolve");
+        fs::create_dir_all(dir.join("packages/ui")).unwrap();
+        fs::write(
+            dir.join("packages/ui/package.json"),
+            r#"{ "name": "@our/ui", "main": "./dist/index.js", "types": "./dist/index.d.ts" }"#,
+        )
+        .unwrap();
+        let all_files: HashSet<String> = [
+            "packages/ui/src/index.ts",
+            "packages/ui/src/button.tsx",
+            "packages/ui/src/theme/index.ts",
+        ]
+        .map(String::from)
+        .into();
+        let resolver = Resolver::new(
+            dir.to_str().unwrap(),
+            &TsConfig::default(),
+            &all_files,
+            HashSet::new(),
+        );
+        let workspace = Workspace {
+            name: "@our/ui".to_string(),
+            dir: "packages/ui".to_string(),
+        };
+        for (subpath, expected) in [
+            ("", Some("packages/ui/src/index.ts")),
+            ("button", Some("packages/ui/src/button.tsx")),
+            ("dist/theme", Some("packages/ui/src/theme/index.ts")),
+            ("missing", None),
+        ] {
+            assert_eq!(
+                resolve_workspace_import(&workspace, subpath, &all_files, &resolver).as_deref(),
+                expected,
+                "{}",
+                subpath
+            );
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+}